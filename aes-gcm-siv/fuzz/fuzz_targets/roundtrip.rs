@@ -0,0 +1,61 @@
+//! Encrypt and decrypt arbitrary messages, checking the plaintext survives.
+//!
+//! Input layout: a selector byte choosing the key size, the key, a 12-byte
+//! nonce, a byte giving the associated data length, then the associated
+//! data followed by the plaintext.
+
+#![no_main]
+
+use aes_gcm_siv::{
+    aead::{Aead, KeyInit, Payload},
+    Aes128GcmSiv, Aes256GcmSiv, Nonce,
+};
+use libfuzzer_sys::fuzz_target;
+
+macro_rules! roundtrip {
+    ($aead:ty, $key_len:expr, $data:expr) => {{
+        let data: &[u8] = $data;
+        if data.len() < $key_len + 13 {
+            return;
+        }
+
+        let (key, data) = data.split_at($key_len);
+        let (nonce, data) = data.split_at(12);
+        let (aad_len, data) = (data[0] as usize, &data[1..]);
+        let (aad, msg) = data.split_at(aad_len.min(data.len()));
+
+        let cipher = <$aead>::new_from_slice(key).unwrap();
+        let nonce = Nonce::from_slice(nonce);
+
+        let ciphertext = cipher.encrypt(nonce, Payload { msg, aad }).unwrap();
+        assert_eq!(ciphertext.len(), msg.len() + 16);
+
+        let payload = Payload {
+            msg: &ciphertext,
+            aad,
+        };
+        assert_eq!(cipher.decrypt(nonce, payload).unwrap(), msg);
+
+        // Lazily generated keystream must match the one-shot output
+        let chunk_size = 1 + aad_len % 37;
+        let chunks: Vec<u8> = cipher
+            .encrypt_chunks(nonce, aad, msg, chunk_size)
+            .unwrap()
+            .flatten()
+            .collect();
+        assert_eq!(chunks, ciphertext);
+    }};
+}
+
+fuzz_target!(|data: &[u8]| {
+    let (&selector, data) = match data.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+
+    if selector & 1 == 0 {
+        roundtrip!(Aes128GcmSiv, 16, data);
+    } else {
+        roundtrip!(Aes256GcmSiv, 32, data);
+    }
+});
@@ -0,0 +1,42 @@
+//! Flip bits in a valid ciphertext or tag, checking decryption fails.
+//!
+//! Input layout: a 32-byte key, a 12-byte nonce, a 4-byte little endian
+//! position, a nonzero XOR mask byte, then the plaintext.
+
+#![no_main]
+
+use aes_gcm_siv::{
+    aead::{Aead, KeyInit},
+    Aes256GcmSiv, Nonce,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 32 + 12 + 4 + 1 {
+        return;
+    }
+
+    let (key, data) = data.split_at(32);
+    let (nonce, data) = data.split_at(12);
+    let (position, data) = data.split_at(4);
+    let (&mask, msg) = data.split_first().unwrap();
+
+    if mask == 0 {
+        return;
+    }
+
+    let cipher = Aes256GcmSiv::new_from_slice(key).unwrap();
+    let nonce = Nonce::from_slice(nonce);
+    let mut ciphertext = cipher.encrypt(nonce, msg).unwrap();
+
+    let position = u32::from_le_bytes(position.try_into().unwrap()) as usize;
+    let len = ciphertext.len();
+    ciphertext[position % len] ^= mask;
+
+    assert!(cipher.decrypt(nonce, ciphertext.as_slice()).is_err());
+
+    // Truncating the ciphertext must also fail
+    assert!(cipher
+        .decrypt(nonce, &ciphertext[..position % len])
+        .is_err());
+});
@@ -0,0 +1,43 @@
+//! AES backend detection.
+
+/// AES implementation used by the built-in AES-GCM-SIV types.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Backend {
+    /// x86/x86_64 AES-NI instructions.
+    AesNi,
+
+    /// ARMv8 Cryptography Extensions.
+    Armv8Crypto,
+
+    /// Portable constant-time (fixsliced) software implementation.
+    Software,
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+    not(aes_force_soft)
+))]
+cpufeatures::new!(aes_intrinsics, "aes");
+
+/// Report which backend the `aes` crate selects on this CPU.
+///
+/// This mirrors the `aes` crate's own selection: hardware instructions are
+/// used when the CPU supports them, unless the crate was built with
+/// `--cfg aes_force_soft` (or, on aarch64, without `--cfg aes_armv8`).
+///
+/// This is purely informational, e.g. for telemetry; every backend produces
+/// identical output.
+pub fn backend_info() -> Backend {
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(aes_force_soft)))]
+    if aes_intrinsics::get() {
+        return Backend::AesNi;
+    }
+
+    #[cfg(all(target_arch = "aarch64", aes_armv8, not(aes_force_soft)))]
+    if aes_intrinsics::get() {
+        return Backend::Armv8Crypto;
+    }
+
+    Backend::Software
+}
@@ -0,0 +1,97 @@
+//! Incremental associated data.
+
+use crate::{AesGcmSiv, Cipher, GcmSivError, Nonce, PaddedInput, Tag};
+use aead::KeyInit;
+use cipher::{consts::U16, BlockCipher, BlockEncrypt};
+
+#[cfg(feature = "alloc")]
+use {alloc::vec::Vec, cipher::Unsigned};
+
+/// Message encryption or decryption whose associated data is supplied
+/// incrementally, created with [`AesGcmSiv::begin`].
+///
+/// Associated data passed to [`AadBuilder::add_aad`] or [`AadBuilder::aad`]
+/// is absorbed as it arrives. The result is identical to passing all of the
+/// pieces concatenated as a single slice: POLYVAL padding is only applied
+/// once, at the end of the associated data.
+pub struct AadBuilder<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt,
+{
+    cipher: Cipher<Aes>,
+    associated_data: PaddedInput,
+}
+
+impl<Aes> AadBuilder<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+    pub(crate) fn new(aead: &AesGcmSiv<Aes>, nonce: &Nonce) -> Self {
+        Self {
+            cipher: Cipher::new(&aead.key_generating_key, nonce),
+            associated_data: PaddedInput::default(),
+        }
+    }
+
+    /// Absorb the next piece of associated data.
+    pub fn add_aad(&mut self, associated_data: &[u8]) -> &mut Self {
+        self.associated_data
+            .update(&mut self.cipher.polyval, associated_data);
+        self
+    }
+
+    /// Absorb the next piece of associated data, taking and returning the
+    /// builder by value so calls can be chained directly off
+    /// [`AesGcmSiv::begin`].
+    pub fn aad(mut self, associated_data: &[u8]) -> Self {
+        self.add_aad(associated_data);
+        self
+    }
+
+    /// Encrypt the given message in-place, returning the authentication tag.
+    pub fn encrypt_in_place_detached(mut self, buffer: &mut [u8]) -> Result<Tag, GcmSivError> {
+        let associated_data_len = self.associated_data.finish(&mut self.cipher.polyval);
+        self.cipher.encrypt_message(associated_data_len, buffer)
+    }
+
+    /// Decrypt the given message in-place, first authenticating it against
+    /// the given tag.
+    pub fn decrypt_in_place_detached(
+        mut self,
+        buffer: &mut [u8],
+        tag: &Tag,
+    ) -> Result<(), GcmSivError> {
+        let associated_data_len = self.associated_data.finish(&mut self.cipher.polyval);
+        self.cipher
+            .decrypt_message(associated_data_len, buffer, tag)
+    }
+
+    /// Encrypt the given plaintext, returning the ciphertext with the tag
+    /// appended.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn encrypt(self, plaintext: &[u8]) -> Result<Vec<u8>, GcmSivError> {
+        let mut buffer = Vec::with_capacity(plaintext.len() + U16::to_usize());
+        buffer.extend_from_slice(plaintext);
+
+        let tag = self.encrypt_in_place_detached(&mut buffer)?;
+        buffer.extend_from_slice(&tag);
+        Ok(buffer)
+    }
+
+    /// Decrypt the given ciphertext (with the tag appended), returning the
+    /// plaintext.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn decrypt(self, ciphertext: &[u8]) -> Result<Vec<u8>, GcmSivError> {
+        let tag_pos = ciphertext
+            .len()
+            .checked_sub(U16::to_usize())
+            .ok_or(GcmSivError::CiphertextTooShort)?;
+
+        let (ciphertext, tag) = ciphertext.split_at(tag_pos);
+        let mut buffer = ciphertext.to_vec();
+        self.decrypt_in_place_detached(&mut buffer, Tag::from_slice(tag))?;
+        Ok(buffer)
+    }
+}
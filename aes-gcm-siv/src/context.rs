@@ -0,0 +1,131 @@
+//! Reusable output buffers.
+
+use crate::{check_ciphertext_lengths, check_lengths, AesGcmSiv, Cipher, GcmSivError, Nonce, Tag};
+use aead::{KeyInit, Payload};
+use alloc::vec::Vec;
+use cipher::{consts::U16, BlockCipher, BlockEncrypt, Unsigned};
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Output buffer which is retained across calls, so that encrypting or
+/// decrypting many messages only allocates when a message is larger than
+/// any seen before.
+///
+/// The slice returned by [`EncryptContext::encrypt`] or
+/// [`EncryptContext::decrypt`] borrows the context and remains valid until
+/// its next use.
+#[derive(Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct EncryptContext {
+    buffer: Vec<u8>,
+
+    #[cfg(feature = "zeroize")]
+    zeroize: bool,
+}
+
+impl EncryptContext {
+    /// Create a new context with an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new context whose buffer can hold messages of up to
+    /// `capacity` bytes (including the tag) without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut context = Self::default();
+        context.buffer.reserve_exact(capacity);
+        context
+    }
+
+    /// Zeroize the previous contents of the buffer before each call and
+    /// when the context is dropped.
+    ///
+    /// Note that when the buffer grows its old allocation is freed without
+    /// being zeroized; use [`EncryptContext::with_capacity`] to avoid this.
+    #[cfg(feature = "zeroize")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+    pub fn zeroize(mut self, enabled: bool) -> Self {
+        self.zeroize = enabled;
+        self
+    }
+
+    /// Encrypt the given plaintext payload into the retained buffer,
+    /// returning the ciphertext with the tag appended.
+    pub fn encrypt<'msg, 'aad, Aes>(
+        &mut self,
+        aead: &AesGcmSiv<Aes>,
+        nonce: &Nonce,
+        plaintext: impl Into<Payload<'msg, 'aad>>,
+    ) -> Result<&[u8], GcmSivError>
+    where
+        Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+    {
+        let payload = plaintext.into();
+        check_lengths(payload.msg.len() as u64, payload.aad.len() as u64)?;
+
+        self.clear();
+        self.buffer.extend_from_slice(payload.msg);
+
+        let tag = Cipher::<Aes>::new(&aead.key_generating_key, nonce)
+            .encrypt_in_place_detached(payload.aad, &mut self.buffer)?;
+
+        self.buffer.extend_from_slice(&tag);
+        Ok(&self.buffer)
+    }
+
+    /// Decrypt the given ciphertext payload (with the tag appended) into the
+    /// retained buffer, returning the plaintext.
+    pub fn decrypt<'msg, 'aad, Aes>(
+        &mut self,
+        aead: &AesGcmSiv<Aes>,
+        nonce: &Nonce,
+        ciphertext: impl Into<Payload<'msg, 'aad>>,
+    ) -> Result<&[u8], GcmSivError>
+    where
+        Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+    {
+        let payload = ciphertext.into();
+        let tag_pos = payload
+            .msg
+            .len()
+            .checked_sub(U16::to_usize())
+            .ok_or(GcmSivError::CiphertextTooShort)?;
+
+        let (ciphertext, tag) = payload.msg.split_at(tag_pos);
+        check_ciphertext_lengths(ciphertext.len() as u64, payload.aad.len() as u64)?;
+
+        self.clear();
+        self.buffer.extend_from_slice(ciphertext);
+
+        let result = Cipher::<Aes>::new(&aead.key_generating_key, nonce).decrypt_in_place_detached(
+            payload.aad,
+            &mut self.buffer,
+            Tag::from_slice(tag),
+        );
+
+        if let Err(err) = result {
+            self.buffer.clear();
+            return Err(err);
+        }
+
+        Ok(&self.buffer)
+    }
+
+    /// Clear the buffer, zeroizing it first if configured to.
+    fn clear(&mut self) {
+        #[cfg(feature = "zeroize")]
+        if self.zeroize {
+            self.buffer.as_mut_slice().zeroize();
+        }
+
+        self.buffer.clear();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for EncryptContext {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
@@ -0,0 +1,137 @@
+//! Key size selected at runtime.
+
+use crate::{Aes128GcmSiv, Aes256GcmSiv, GcmSivError, Nonce, Tag};
+use aead::{AeadCore, AeadInPlace, KeyInit};
+use cipher::consts::{U0, U12, U16};
+
+#[cfg(feature = "non-standard-sizes")]
+use crate::Aes192GcmSiv;
+
+/// AES-GCM-SIV with the key size chosen from the length of the key, for
+/// callers whose keys aren't all the same size.
+///
+/// Each variant produces exactly the same output as the corresponding
+/// concrete type.
+///
+/// ```
+/// # fn main() -> Result<(), aes_gcm_siv::Error> {
+/// use aes_gcm_siv::{aead::Aead, GcmSivKey, Nonce};
+///
+/// let cipher = GcmSivKey::new(&[0x42; 32])?;
+/// assert!(matches!(cipher, GcmSivKey::Aes256(_)));
+///
+/// let nonce = Nonce::from_slice(b"unique nonce");
+/// let ciphertext = cipher.encrypt(nonce, &b"plaintext"[..])?;
+/// assert_eq!(cipher.decrypt(nonce, ciphertext.as_slice())?, b"plaintext");
+/// # Ok(())
+/// # }
+/// ```
+// Boxing the larger variants would require `alloc`
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum GcmSivKey {
+    /// 128-bit key.
+    Aes128(Aes128GcmSiv),
+
+    /// 192-bit key.
+    #[cfg(feature = "non-standard-sizes")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "non-standard-sizes")))]
+    Aes192(Aes192GcmSiv),
+
+    /// 256-bit key.
+    Aes256(Aes256GcmSiv),
+}
+
+impl GcmSivKey {
+    /// Create a cipher whose key size matches the length of `key`.
+    ///
+    /// Returns [`GcmSivError::InvalidKeyLength`] for any other length.
+    pub fn new(key: &[u8]) -> Result<Self, GcmSivError> {
+        match key.len() {
+            crate::AES128_KEY_SIZE => Ok(Self::Aes128(Aes128GcmSiv::new_from_slice(key)?)),
+            #[cfg(feature = "non-standard-sizes")]
+            crate::AES192_KEY_SIZE => Ok(Self::Aes192(Aes192GcmSiv::new_from_slice(key)?)),
+            crate::AES256_KEY_SIZE => Ok(Self::Aes256(Aes256GcmSiv::new_from_slice(key)?)),
+            _ => Err(GcmSivError::InvalidKeyLength),
+        }
+    }
+
+    /// Size of the key in bytes.
+    pub fn key_size(&self) -> usize {
+        match self {
+            Self::Aes128(_) => crate::AES128_KEY_SIZE,
+            #[cfg(feature = "non-standard-sizes")]
+            Self::Aes192(_) => crate::AES192_KEY_SIZE,
+            Self::Aes256(_) => crate::AES256_KEY_SIZE,
+        }
+    }
+}
+
+impl From<Aes128GcmSiv> for GcmSivKey {
+    fn from(cipher: Aes128GcmSiv) -> Self {
+        Self::Aes128(cipher)
+    }
+}
+
+#[cfg(feature = "non-standard-sizes")]
+impl From<Aes192GcmSiv> for GcmSivKey {
+    fn from(cipher: Aes192GcmSiv) -> Self {
+        Self::Aes192(cipher)
+    }
+}
+
+impl From<Aes256GcmSiv> for GcmSivKey {
+    fn from(cipher: Aes256GcmSiv) -> Self {
+        Self::Aes256(cipher)
+    }
+}
+
+impl AeadCore for GcmSivKey {
+    type NonceSize = U12;
+    type TagSize = U16;
+    type CiphertextOverhead = U0;
+}
+
+impl AeadInPlace for GcmSivKey {
+    fn encrypt_in_place_detached(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<Tag, aead::Error> {
+        match self {
+            Self::Aes128(cipher) => {
+                cipher.encrypt_in_place_detached(nonce, associated_data, buffer)
+            }
+            #[cfg(feature = "non-standard-sizes")]
+            Self::Aes192(cipher) => {
+                cipher.encrypt_in_place_detached(nonce, associated_data, buffer)
+            }
+            Self::Aes256(cipher) => {
+                cipher.encrypt_in_place_detached(nonce, associated_data, buffer)
+            }
+        }
+    }
+
+    fn decrypt_in_place_detached(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &Tag,
+    ) -> Result<(), aead::Error> {
+        match self {
+            Self::Aes128(cipher) => {
+                cipher.decrypt_in_place_detached(nonce, associated_data, buffer, tag)
+            }
+            #[cfg(feature = "non-standard-sizes")]
+            Self::Aes192(cipher) => {
+                cipher.decrypt_in_place_detached(nonce, associated_data, buffer, tag)
+            }
+            Self::Aes256(cipher) => {
+                cipher.decrypt_in_place_detached(nonce, associated_data, buffer, tag)
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Error types.
+
+use core::fmt;
+
+/// Detailed AES-GCM-SIV error.
+///
+/// The inherent methods of [`AesGcmSiv`][crate::AesGcmSiv] return this type
+/// so callers can distinguish malformed inputs from authentication failures.
+/// It converts into the opaque [`aead::Error`] returned by the [`aead`]
+/// traits.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum GcmSivError {
+    /// Associated data is longer than [`A_MAX`][crate::A_MAX].
+    AadTooLong,
+
+    /// Plaintext is longer than [`P_MAX`][crate::P_MAX].
+    PlaintextTooLong,
+
+    /// Ciphertext is longer than [`C_MAX`][crate::C_MAX] (or
+    /// [`P_MAX`][crate::P_MAX] if the tag is detached).
+    CiphertextTooLong,
+
+    /// Ciphertext is shorter than the authentication tag.
+    CiphertextTooShort,
+
+    /// Ciphertext failed to authenticate.
+    AuthFailed,
+
+    /// Output buffer is too small to hold the result.
+    BufferTooSmall,
+
+    /// Allocating the output buffer failed.
+    AllocationFailed,
+
+    /// Key passed to [`KeyInit::new_from_slice`][aead::KeyInit::new_from_slice]
+    /// has the wrong length.
+    InvalidKeyLength,
+
+    /// A separate tag was supplied when the
+    /// [`EncryptOptions`][crate::EncryptOptions] place it in the ciphertext,
+    /// or was missing when they specify a detached tag.
+    TagPlacement,
+
+    /// A known-answer self-test produced the wrong output.
+    SelfTestFailed,
+
+    /// A [`StreamingEncryptor`][crate::StreamingEncryptor] was used out of
+    /// order, or its two passes covered different amounts of data.
+    StreamMismatch,
+
+    /// A `TrackedCipher` (available with the `std` feature) was asked to
+    /// encrypt under a nonce it had already used.
+    NonceReused,
+}
+
+impl fmt::Display for GcmSivError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            GcmSivError::AadTooLong => "associated data too long",
+            GcmSivError::PlaintextTooLong => "plaintext too long",
+            GcmSivError::CiphertextTooLong => "ciphertext too long",
+            GcmSivError::CiphertextTooShort => "ciphertext shorter than the tag",
+            GcmSivError::AuthFailed => "ciphertext failed to authenticate",
+            GcmSivError::BufferTooSmall => "output buffer too small",
+            GcmSivError::AllocationFailed => "output buffer allocation failed",
+            GcmSivError::InvalidKeyLength => "invalid key length",
+            GcmSivError::TagPlacement => "tag placement doesn't match options",
+            GcmSivError::SelfTestFailed => "known-answer self-test failed",
+            GcmSivError::StreamMismatch => "streaming encryptor used out of order",
+            GcmSivError::NonceReused => "nonce reused for encryption",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for GcmSivError {}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl From<GcmSivError> for std::io::Error {
+    fn from(err: GcmSivError) -> std::io::Error {
+        let kind = match err {
+            GcmSivError::AuthFailed
+            | GcmSivError::CiphertextTooLong
+            | GcmSivError::CiphertextTooShort => std::io::ErrorKind::InvalidData,
+            _ => std::io::ErrorKind::InvalidInput,
+        };
+
+        std::io::Error::new(kind, err)
+    }
+}
+
+impl From<GcmSivError> for aead::Error {
+    fn from(_: GcmSivError) -> aead::Error {
+        aead::Error
+    }
+}
+
+impl From<cipher::InvalidLength> for GcmSivError {
+    fn from(_: cipher::InvalidLength) -> GcmSivError {
+        GcmSivError::InvalidKeyLength
+    }
+}
@@ -0,0 +1,281 @@
+//! [`std::io`] adapters.
+
+#[cfg(feature = "stream")]
+mod stream;
+
+#[cfg(feature = "stream")]
+pub use self::stream::{StreamDecryptReader, StreamEncryptWriter, StreamNonce};
+
+use crate::{AesGcmSiv, Cipher, GcmSivError, Nonce, Tag, C_MAX, P_MAX};
+use aead::KeyInit;
+use alloc::vec::Vec;
+use cipher::{consts::U16, BlockCipher, BlockEncrypt, Unsigned};
+use std::{
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::Path,
+};
+
+/// [`Write`] adapter which encrypts everything written to it.
+///
+/// AES-GCM-SIV can't produce any ciphertext until it has seen the entire
+/// plaintext, so all writes are buffered in memory. The ciphertext followed
+/// by the tag is written to the underlying writer by
+/// [`EncryptWriter::finish`]; dropping the adapter without calling it
+/// discards the message.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct EncryptWriter<Aes, W> {
+    aead: AesGcmSiv<Aes>,
+    nonce: Nonce,
+    associated_data: Vec<u8>,
+    buffer: Vec<u8>,
+    inner: W,
+}
+
+impl<Aes, W> EncryptWriter<Aes, W>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+    W: Write,
+{
+    /// Create a new adapter which encrypts under the given nonce and
+    /// associated data, writing the result to `inner`.
+    pub fn new(aead: AesGcmSiv<Aes>, nonce: &Nonce, associated_data: &[u8], inner: W) -> Self {
+        Self {
+            aead,
+            nonce: *nonce,
+            associated_data: associated_data.to_vec(),
+            buffer: Vec::new(),
+            inner,
+        }
+    }
+
+    /// Encrypt the buffered plaintext, write the ciphertext and tag to the
+    /// underlying writer, and return it.
+    pub fn finish(mut self) -> io::Result<W> {
+        let tag = Cipher::<Aes>::new(&self.aead.key_generating_key, &self.nonce)
+            .encrypt_in_place_detached(&self.associated_data, &mut self.buffer)
+            .map_err(io::Error::from)?;
+
+        self.inner.write_all(&self.buffer)?;
+        self.inner.write_all(&tag)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<Aes, W> Write for EncryptWriter<Aes, W>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.buffer.len() as u64 + buf.len() as u64 > P_MAX {
+            return Err(GcmSivError::PlaintextTooLong.into());
+        }
+
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // Nothing can be written until `finish`
+        Ok(())
+    }
+}
+
+/// [`Read`] adapter which decrypts a message read from the underlying
+/// reader.
+///
+/// The first read consumes the underlying reader to EOF and authenticates
+/// the entire message (ciphertext followed by tag) before any plaintext is
+/// returned.
+///
+/// If the underlying reader fails partway through (e.g. with
+/// [`io::ErrorKind::WouldBlock`]), the ciphertext read so far is kept and
+/// the next read carries on from where it stopped. Once the message fails
+/// to decrypt, every later read returns the same error.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct DecryptReader<Aes, R> {
+    aead: AesGcmSiv<Aes>,
+    nonce: Nonce,
+    associated_data: Vec<u8>,
+    inner: R,
+
+    /// Ciphertext read so far, replaced by the plaintext once decrypted.
+    buffer: Vec<u8>,
+
+    /// Whether `buffer` holds the decrypted plaintext.
+    decrypted: bool,
+
+    /// Number of bytes of plaintext already returned.
+    pos: usize,
+
+    /// Why the message failed to decrypt, if it did.
+    poisoned: Option<GcmSivError>,
+}
+
+impl<Aes, R> DecryptReader<Aes, R>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+    R: Read,
+{
+    /// Create a new adapter which decrypts the message read from `inner`
+    /// under the given nonce and associated data.
+    pub fn new(aead: AesGcmSiv<Aes>, nonce: &Nonce, associated_data: &[u8], inner: R) -> Self {
+        Self {
+            aead,
+            nonce: *nonce,
+            associated_data: associated_data.to_vec(),
+            inner,
+            buffer: Vec::new(),
+            decrypted: false,
+            pos: 0,
+            poisoned: None,
+        }
+    }
+
+    /// Read the rest of the message from the underlying reader and decrypt
+    /// it in place.
+    fn decrypt(&mut self) -> io::Result<()> {
+        // On error, `read_to_end` leaves whatever it did read in `buffer`
+        let limit = (C_MAX + 1).saturating_sub(self.buffer.len() as u64);
+        (&mut self.inner)
+            .take(limit)
+            .read_to_end(&mut self.buffer)?;
+
+        if let Err(err) = self.decrypt_buffer() {
+            self.buffer.clear();
+            self.poisoned = Some(err);
+            return Err(err.into());
+        }
+
+        self.decrypted = true;
+        Ok(())
+    }
+
+    /// Authenticate and decrypt the complete message held in `buffer`.
+    fn decrypt_buffer(&mut self) -> Result<(), GcmSivError> {
+        if self.buffer.len() as u64 > C_MAX {
+            return Err(GcmSivError::CiphertextTooLong);
+        }
+
+        let tag_pos = self
+            .buffer
+            .len()
+            .checked_sub(U16::to_usize())
+            .ok_or(GcmSivError::CiphertextTooShort)?;
+
+        let tag = Tag::clone_from_slice(&self.buffer[tag_pos..]);
+        self.buffer.truncate(tag_pos);
+
+        Cipher::<Aes>::new(&self.aead.key_generating_key, &self.nonce).decrypt_in_place_detached(
+            &self.associated_data,
+            &mut self.buffer,
+            &tag,
+        )
+    }
+}
+
+impl<Aes, R> Read for DecryptReader<Aes, R>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let Some(err) = self.poisoned {
+            return Err(err.into());
+        }
+
+        if !self.decrypted {
+            self.decrypt()?;
+        }
+
+        let remaining = &self.buffer[self.pos..];
+        let n = core::cmp::min(remaining.len(), buf.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl<Aes> AesGcmSiv<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+    /// Encrypt the file at `src`, writing the ciphertext followed by the tag
+    /// to `dst`.
+    ///
+    /// AES-GCM-SIV needs the entire plaintext before producing any
+    /// ciphertext, so the whole file is read into memory: peak memory use is
+    /// the size of the file plus the tag. Files longer than `max_len` bytes
+    /// are rejected with [`io::ErrorKind::InvalidInput`] without reading
+    /// them in full, and `dst` is not created.
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn encrypt_file(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        src: impl AsRef<Path>,
+        dst: impl AsRef<Path>,
+        max_len: u64,
+    ) -> io::Result<()> {
+        let mut buffer = read_limited(src.as_ref(), max_len, io::ErrorKind::InvalidInput)?;
+
+        let tag = Cipher::<Aes>::new(&self.key_generating_key, nonce)
+            .encrypt_in_place_detached(associated_data, &mut buffer)?;
+
+        buffer.extend_from_slice(&tag);
+        fs::write(dst, &buffer)
+    }
+
+    /// Decrypt a file written by [`AesGcmSiv::encrypt_file`], writing the
+    /// plaintext to `dst`.
+    ///
+    /// As with encryption, the whole file is read into memory. Files whose
+    /// plaintext would be longer than `max_len` bytes are rejected with
+    /// [`io::ErrorKind::InvalidData`], and `dst` is only created once the
+    /// ciphertext has been authenticated.
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn decrypt_file(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        src: impl AsRef<Path>,
+        dst: impl AsRef<Path>,
+        max_len: u64,
+    ) -> io::Result<()> {
+        let max_len = max_len.saturating_add(U16::to_u64());
+        let mut buffer = read_limited(src.as_ref(), max_len, io::ErrorKind::InvalidData)?;
+
+        let tag_pos = buffer
+            .len()
+            .checked_sub(U16::to_usize())
+            .ok_or(GcmSivError::CiphertextTooShort)?;
+
+        let tag = Tag::clone_from_slice(&buffer[tag_pos..]);
+        buffer.truncate(tag_pos);
+
+        Cipher::<Aes>::new(&self.key_generating_key, nonce).decrypt_in_place_detached(
+            associated_data,
+            &mut buffer,
+            &tag,
+        )?;
+
+        fs::write(dst, &buffer)
+    }
+}
+
+/// Read the file at `path`, failing with an error of the given kind if it's
+/// longer than `max_len` bytes.
+fn read_limited(path: &Path, max_len: u64, kind: io::ErrorKind) -> io::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    File::open(path)?
+        .take(max_len.saturating_add(1))
+        .read_to_end(&mut buffer)?;
+
+    if buffer.len() as u64 > max_len {
+        return Err(io::Error::new(kind, "file exceeds the maximum length"));
+    }
+
+    Ok(buffer)
+}
@@ -0,0 +1,303 @@
+//! [`std::io`] adapters for the STREAM construction.
+
+use crate::AesGcmSiv;
+use crate::GcmSivError;
+use aead::{
+    stream::{DecryptorBE32, EncryptorBE32},
+    KeyInit,
+};
+use alloc::vec::Vec;
+use cipher::{
+    consts::{U16, U7},
+    generic_array::GenericArray,
+    BlockCipher, BlockEncrypt, Unsigned,
+};
+use core::mem;
+use std::io::{self, Read, Write};
+
+/// Nonce prefix for the STREAM adapters.
+///
+/// The remaining 5 bytes of each segment's AES-GCM-SIV nonce are a 32-bit
+/// big endian segment counter and a last-segment flag.
+pub type StreamNonce = GenericArray<u8, U7>;
+
+/// [`Write`] adapter which encrypts everything written to it as a sequence
+/// of STREAM segments.
+///
+/// Unlike [`EncryptWriter`][crate::EncryptWriter], only one segment of
+/// plaintext is buffered at a time: each full segment is written to the
+/// underlying writer as `ciphertext || tag` once more data follows it, and
+/// [`StreamEncryptWriter::finish`] writes the final (possibly short or
+/// empty) segment with the last-segment flag set. The output can be
+/// decrypted with [`StreamDecryptReader`], or with
+/// [`aead::stream::DecryptorBE32`] in segments of `segment_size + 16` bytes.
+///
+/// If the underlying writer fails (e.g. with
+/// [`io::ErrorKind::WouldBlock`]), the unwritten part of the encrypted
+/// segment is kept and written before anything else, so the write can be
+/// retried.
+///
+/// Dropping the adapter without calling [`StreamEncryptWriter::finish`]
+/// leaves a stream with no final segment, which fails to decrypt.
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std", feature = "stream"))))]
+pub struct StreamEncryptWriter<Aes, W>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+    encryptor: EncryptorBE32<AesGcmSiv<Aes>>,
+    associated_data: Vec<u8>,
+    segment_size: usize,
+
+    /// Plaintext of the segment being filled.
+    buffer: Vec<u8>,
+
+    /// Encrypted segment not yet accepted by the underlying writer.
+    output: Vec<u8>,
+
+    /// Number of bytes of `output` already written.
+    output_pos: usize,
+
+    inner: W,
+}
+
+impl<Aes, W> StreamEncryptWriter<Aes, W>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+    W: Write,
+{
+    /// Create a new adapter which encrypts `segment_size` bytes of plaintext
+    /// per segment, authenticating every segment with the given associated
+    /// data, and writes the result to `inner`.
+    ///
+    /// # Panics
+    ///
+    /// If `segment_size` is zero.
+    pub fn new(
+        aead: AesGcmSiv<Aes>,
+        nonce: &StreamNonce,
+        associated_data: &[u8],
+        segment_size: usize,
+        inner: W,
+    ) -> Self {
+        assert!(segment_size > 0, "segment size must be non-zero");
+
+        Self {
+            encryptor: EncryptorBE32::from_aead(aead, nonce),
+            associated_data: associated_data.to_vec(),
+            segment_size,
+            buffer: Vec::with_capacity(segment_size + U16::to_usize()),
+            output: Vec::with_capacity(segment_size + U16::to_usize()),
+            output_pos: 0,
+            inner,
+        }
+    }
+
+    /// Encrypt and write the final segment, returning the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_output()?;
+
+        self.encryptor
+            .encrypt_last_in_place(&self.associated_data, &mut self.buffer)
+            .map_err(into_io_error)?;
+
+        self.inner.write_all(&self.buffer)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Encrypt the buffered segment, which isn't the last one, and move it
+    /// to `output` to be written.
+    fn write_segment(&mut self) -> io::Result<()> {
+        debug_assert!(self.output.is_empty());
+
+        self.encryptor
+            .encrypt_next_in_place(&self.associated_data, &mut self.buffer)
+            .map_err(into_io_error)?;
+
+        mem::swap(&mut self.buffer, &mut self.output);
+        self.write_output()
+    }
+
+    /// Write out the rest of `output`, keeping track of how much the
+    /// underlying writer has accepted in case it fails partway.
+    fn write_output(&mut self) -> io::Result<()> {
+        while self.output_pos < self.output.len() {
+            match self.inner.write(&self.output[self.output_pos..]) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => self.output_pos += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) => return Err(err),
+            }
+        }
+
+        self.output.clear();
+        self.output_pos = 0;
+        Ok(())
+    }
+}
+
+impl<Aes, W> Write for StreamEncryptWriter<Aes, W>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_output()?;
+
+        // A full segment is only known not to be the last one once more
+        // plaintext arrives
+        if self.buffer.len() == self.segment_size && !buf.is_empty() {
+            self.write_segment()?;
+        }
+
+        let n = core::cmp::min(buf.len(), self.segment_size - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_output()?;
+        self.inner.flush()
+    }
+}
+
+/// [`Read`] adapter which decrypts a sequence of STREAM segments written by
+/// [`StreamEncryptWriter`].
+///
+/// Each segment is authenticated before any of its plaintext is returned,
+/// so at most one segment of plaintext is buffered. A segment which fails
+/// to authenticate (including a stream which was truncated, or whose final
+/// segment is missing) fails the read with [`io::ErrorKind::InvalidData`],
+/// as does every read after it.
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std", feature = "stream"))))]
+pub struct StreamDecryptReader<Aes, R>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+    /// Decryptor for the remaining segments; `None` after the last segment
+    /// or an authentication failure.
+    decryptor: Option<DecryptorBE32<AesGcmSiv<Aes>>>,
+    associated_data: Vec<u8>,
+    segment_size: usize,
+    inner: R,
+
+    /// Bytes read from `inner` which haven't been decrypted yet.
+    pending: Vec<u8>,
+
+    /// Plaintext of the current segment.
+    plaintext: Vec<u8>,
+
+    /// Number of bytes of `plaintext` already returned.
+    pos: usize,
+
+    /// Whether a segment failed to authenticate.
+    poisoned: bool,
+}
+
+impl<Aes, R> StreamDecryptReader<Aes, R>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+    R: Read,
+{
+    /// Create a new adapter which decrypts the stream read from `inner`,
+    /// using the same nonce, associated data and segment size it was
+    /// encrypted with.
+    ///
+    /// # Panics
+    ///
+    /// If `segment_size` is zero.
+    pub fn new(
+        aead: AesGcmSiv<Aes>,
+        nonce: &StreamNonce,
+        associated_data: &[u8],
+        segment_size: usize,
+        inner: R,
+    ) -> Self {
+        assert!(segment_size > 0, "segment size must be non-zero");
+
+        Self {
+            decryptor: Some(DecryptorBE32::from_aead(aead, nonce)),
+            associated_data: associated_data.to_vec(),
+            segment_size,
+            inner,
+            pending: Vec::new(),
+            plaintext: Vec::new(),
+            pos: 0,
+            poisoned: false,
+        }
+    }
+
+    /// Read and decrypt the next segment into `self.plaintext`.
+    fn next_segment(&mut self) -> io::Result<()> {
+        let segment_len = self.segment_size + U16::to_usize();
+
+        // Read one byte past the segment to tell whether it's the last one
+        let wanted = segment_len + 1 - self.pending.len();
+        (&mut self.inner)
+            .take(wanted as u64)
+            .read_to_end(&mut self.pending)?;
+
+        let (mut segment, result) = if self.pending.len() > segment_len {
+            let rest = self.pending.split_off(segment_len);
+            let mut segment = mem::replace(&mut self.pending, rest);
+            let result = self
+                .decryptor
+                .as_mut()
+                .expect("stream not finished")
+                .decrypt_next_in_place(&self.associated_data, &mut segment);
+            (segment, result)
+        } else {
+            let mut segment = mem::take(&mut self.pending);
+            let result = self
+                .decryptor
+                .take()
+                .expect("stream not finished")
+                .decrypt_last_in_place(&self.associated_data, &mut segment);
+            (segment, result)
+        };
+
+        if result.is_err() {
+            self.decryptor = None;
+            self.poisoned = true;
+            segment.clear();
+            return Err(GcmSivError::AuthFailed.into());
+        }
+
+        self.plaintext = segment;
+        self.pos = 0;
+        Ok(())
+    }
+}
+
+impl<Aes, R> Read for StreamDecryptReader<Aes, R>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.plaintext.len() {
+            if self.poisoned {
+                return Err(GcmSivError::AuthFailed.into());
+            }
+
+            if self.decryptor.is_none() {
+                return Ok(0);
+            }
+
+            self.next_segment()?;
+        }
+
+        let remaining = &self.plaintext[self.pos..];
+        let n = core::cmp::min(remaining.len(), buf.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// Convert a STREAM error into an I/O error.
+///
+/// Encryption only fails once the 32-bit segment counter is exhausted.
+fn into_io_error(err: aead::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err)
+}
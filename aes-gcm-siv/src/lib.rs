@@ -40,7 +40,7 @@
 //!
 //! Note that if you enable the `heapless` feature of this crate,
 //! you will receive an impl of [`aead::Buffer`] for `heapless::Vec`
-//! (re-exported from the [`aead`] crate as [`aead::heapless::Vec`]),
+//! (re-exported from the [`aead`] crate as `aead::heapless::Vec`),
 //! which can then be passed as the `buffer` parameter to the in-place encrypt
 //! and decrypt methods:
 //!
@@ -80,22 +80,142 @@
 //!
 //! Similarly, enabling the `arrayvec` feature of this crate will provide an impl of
 //! [`aead::Buffer`] for `arrayvec::ArrayVec` (re-exported from the [`aead`] crate as
-//! [`aead::arrayvec::ArrayVec`]).
+//! `aead::arrayvec::ArrayVec`), and the `bytes` feature an impl for
+//! `bytes::BytesMut` (re-exported as `aead::bytes::BytesMut`), which grows
+//! as needed to make room for the tag.
+//!
+//! ## Detached Tags
+//!
+//! The [`AeadInPlace::encrypt_in_place_detached`] and
+//! [`AeadInPlace::decrypt_in_place_detached`] methods operate on a plain
+//! `&mut [u8]` and return (or accept) the authentication [`Tag`] separately,
+//! for protocols which carry the tag apart from the ciphertext:
+//!
+#![cfg_attr(feature = "aes", doc = "```")]
+#![cfg_attr(not(feature = "aes"), doc = "```ignore")]
+//! # fn main() -> Result<(), aes_gcm_siv::Error> {
+//! use aes_gcm_siv::{
+//!     aead::{AeadInPlace, KeyInit},
+//!     Aes256GcmSiv, Nonce, // Or `Aes128GcmSiv`
+//! };
+//!
+//! let cipher = Aes256GcmSiv::new(&Default::default());
+//! let nonce = Nonce::from_slice(b"unique nonce"); // 96-bits; unique per message
+//!
+//! let mut buffer = *b"plaintext message";
+//! let tag = cipher.encrypt_in_place_detached(nonce, b"", &mut buffer)?;
+//! assert_ne!(&buffer, b"plaintext message");
+//!
+//! cipher.decrypt_in_place_detached(nonce, b"", &mut buffer, &tag)?;
+//! assert_eq!(&buffer, b"plaintext message");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Deterministic Encryption
+//!
+//! For uses such as key wrapping, where encrypting the same value twice
+//! should produce the same ciphertext, AES-GCM-SIV can be used with a fixed
+//! nonce. The ciphertext then depends only on the key, associated data and
+//! plaintext, so any context the result should be bound to (e.g. a label
+//! or record identifier) belongs in the associated data; there is no need
+//! to derive a nonce from it.
+//!
+//! The trade-off is privacy: anyone who sees two ciphertexts under the same
+//! key, nonce and associated data learns whether the plaintexts are equal.
+//! Use unique nonces whenever this matters.
+//!
+#![cfg_attr(all(feature = "aes", feature = "alloc"), doc = "```")]
+#![cfg_attr(not(all(feature = "aes", feature = "alloc")), doc = "```ignore")]
+//! # fn main() -> Result<(), aes_gcm_siv::Error> {
+//! use aes_gcm_siv::{
+//!     aead::{Aead, KeyInit, Payload},
+//!     Aes256GcmSiv, Nonce,
+//! };
+//!
+//! let cipher = Aes256GcmSiv::new(&Default::default());
+//! let nonce = Nonce::default(); // Fixed: this is deterministic encryption
+//! let wrap = |key: &[u8]| cipher.encrypt(&nonce, Payload { msg: key, aad: b"wrapped-key/v1" });
+//!
+//! assert_eq!(wrap(b"data key")?, wrap(b"data key")?);
+//! assert_ne!(wrap(b"data key")?, wrap(b"other key")?);
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "aes")]
+mod backend;
+mod builder;
+#[cfg(feature = "alloc")]
+mod context;
+#[cfg(feature = "aes")]
+mod dynamic;
+mod error;
+#[cfg(feature = "std")]
+mod io;
+mod options;
+mod per_nonce;
+#[cfg(feature = "self-test")]
+mod self_test;
+mod streaming;
+#[cfg(feature = "std")]
+mod tracked;
+
+pub use crate::{
+    builder::AadBuilder, error::GcmSivError, options::AuthFailurePolicy, per_nonce::PerNonceKeys,
+    streaming::StreamingEncryptor,
+};
 
+#[cfg(all(feature = "std", feature = "stream"))]
+pub use crate::io::{StreamDecryptReader, StreamEncryptWriter, StreamNonce};
+#[cfg(feature = "self-test")]
+#[cfg_attr(docsrs, doc(cfg(feature = "self-test")))]
+pub use crate::self_test::run_rfc8452_vectors;
+#[cfg(feature = "aes")]
+pub use crate::{
+    backend::{backend_info, Backend},
+    dynamic::GcmSivKey,
+};
+#[cfg(feature = "alloc")]
+pub use crate::{
+    context::EncryptContext,
+    options::{EncryptOptions, TagPosition},
+    streaming::EncryptChunks,
+};
+#[cfg(feature = "std")]
+pub use crate::{
+    io::{DecryptReader, EncryptWriter},
+    tracked::{NonceReusePolicy, TrackedCipher},
+};
 pub use aead::{self, AeadCore, AeadInPlace, Error, Key, KeyInit, KeySizeUser};
 
 use cipher::{
     consts::{U0, U12, U16},
     generic_array::GenericArray,
-    BlockCipher, BlockEncrypt, InnerIvInit, StreamCipherCore,
+    BlockCipher, BlockEncrypt, InnerIvInit, StreamCipherCore, Unsigned,
 };
+use core::{fmt, mem::MaybeUninit};
 use polyval::{universal_hash::UniversalHash, Polyval};
+
+#[cfg(feature = "alloc")]
+use {aead::Payload, alloc::vec::Vec};
+
+#[cfg(feature = "zeroize")]
 use zeroize::Zeroize;
 
 /// AES is optional to allow swapping in hardware-specific backends.
 #[cfg(feature = "aes")]
 use aes::{Aes128, Aes256};
 
+#[cfg(feature = "non-standard-sizes")]
+use aes::Aes192;
+
 /// Maximum length of associated data (from RFC8452 § 6).
 pub const A_MAX: u64 = 1 << 36;
 
@@ -105,6 +225,30 @@ pub const P_MAX: u64 = 1 << 36;
 /// Maximum length of ciphertext (from RFC8452 § 6).
 pub const C_MAX: u64 = (1 << 36) + 16;
 
+/// Size of an AES-GCM-SIV nonce in bytes.
+///
+/// ```
+/// assert_eq!(aes_gcm_siv::NONCE_SIZE, 12);
+/// assert_eq!(aes_gcm_siv::TAG_SIZE, 16);
+/// ```
+pub const NONCE_SIZE: usize = U12::USIZE;
+
+/// Size of an AES-GCM-SIV authentication tag in bytes.
+pub const TAG_SIZE: usize = U16::USIZE;
+
+/// Size of an [`Aes128GcmSiv`] key in bytes.
+#[cfg(feature = "aes")]
+pub const AES128_KEY_SIZE: usize = <<Aes128 as KeySizeUser>::KeySize as Unsigned>::USIZE;
+
+/// Size of an [`Aes192GcmSiv`] key in bytes.
+#[cfg(feature = "non-standard-sizes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "non-standard-sizes")))]
+pub const AES192_KEY_SIZE: usize = <<Aes192 as KeySizeUser>::KeySize as Unsigned>::USIZE;
+
+/// Size of an [`Aes256GcmSiv`] key in bytes.
+#[cfg(feature = "aes")]
+pub const AES256_KEY_SIZE: usize = <<Aes256 as KeySizeUser>::KeySize as Unsigned>::USIZE;
+
 /// AES-GCM-SIV nonces.
 pub type Nonce = GenericArray<u8, U12>;
 
@@ -115,14 +259,39 @@ pub type Tag = GenericArray<u8, U16>;
 #[cfg(feature = "aes")]
 pub type Aes128GcmSiv = AesGcmSiv<Aes128>;
 
+/// AES-GCM-SIV with a 192-bit key.
+///
+/// RFC 8452 only specifies 128-bit and 256-bit keys. This variant follows
+/// the same construction, deriving a 192-bit message-encryption key from
+/// counter values 2 through 4, but is not standardized.
+#[cfg(feature = "non-standard-sizes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "non-standard-sizes")))]
+pub type Aes192GcmSiv = AesGcmSiv<Aes192>;
+
 /// AES-GCM-SIV with a 256-bit key.
 #[cfg(feature = "aes")]
 pub type Aes256GcmSiv = AesGcmSiv<Aes256>;
 
+/// GCM-SIV over an arbitrary block cipher with a 128-bit block size.
+///
+/// This is the same type as [`AesGcmSiv`], under a name which doesn't imply
+/// AES. RFC 8452 only specifies the construction with AES, so instantiating
+/// it with any other cipher is not standardized and won't interoperate with
+/// other implementations unless they make the same choices.
+pub type GcmSiv<C> = AesGcmSiv<C>;
+
 /// Counter mode with a 32-bit little endian counter.
 type Ctr32LE<Aes> = ctr::CtrCore<Aes, ctr::flavors::Ctr32LE>;
 
 /// AES-GCM-SIV: Misuse-Resistant Authenticated Encryption Cipher (RFC 8452).
+///
+/// When the `zeroize` feature is enabled, this type impls [`ZeroizeOnDrop`]
+/// whenever the underlying block cipher does, i.e. the expanded
+/// key-generating-key is wiped when the cipher (or any clone of it) is
+/// dropped. This includes the built-in AES types, for which the `zeroize`
+/// feature of the `aes` crate is enabled automatically.
+///
+/// [`ZeroizeOnDrop`]: https://docs.rs/zeroize/latest/zeroize/trait.ZeroizeOnDrop.html
 #[derive(Clone)]
 pub struct AesGcmSiv<Aes> {
     /// Key generating key used to derive AES-GCM-SIV subkeys.
@@ -156,6 +325,42 @@ where
     }
 }
 
+#[cfg(feature = "aes")]
+impl From<[u8; 16]> for Aes128GcmSiv {
+    fn from(key: [u8; 16]) -> Self {
+        Self::new(&key.into())
+    }
+}
+
+#[cfg(feature = "non-standard-sizes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "non-standard-sizes")))]
+impl From<[u8; 24]> for Aes192GcmSiv {
+    fn from(key: [u8; 24]) -> Self {
+        Self::new(&key.into())
+    }
+}
+
+#[cfg(feature = "aes")]
+impl From<[u8; 32]> for Aes256GcmSiv {
+    fn from(key: [u8; 32]) -> Self {
+        Self::new(&key.into())
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<Aes> zeroize::ZeroizeOnDrop for AesGcmSiv<Aes> where Aes: zeroize::ZeroizeOnDrop {}
+
+/// Prints `AesGcmSiv { key: <redacted> }`, so the cipher can be embedded in
+/// types which derive `Debug` without leaking key material.
+impl<Aes> fmt::Debug for AesGcmSiv<Aes> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AesGcmSiv")
+            .field("key", &format_args!("<redacted>"))
+            .finish()
+    }
+}
+
 impl<Aes> AeadCore for AesGcmSiv<Aes>
 where
     Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
@@ -177,6 +382,7 @@ where
     ) -> Result<Tag, Error> {
         Cipher::<Aes>::new(&self.key_generating_key, nonce)
             .encrypt_in_place_detached(associated_data, buffer)
+            .map_err(Into::into)
     }
 
     fn decrypt_in_place_detached(
@@ -186,165 +392,1256 @@ where
         buffer: &mut [u8],
         tag: &Tag,
     ) -> Result<(), Error> {
-        Cipher::<Aes>::new(&self.key_generating_key, nonce).decrypt_in_place_detached(
-            associated_data,
-            buffer,
-            tag,
-        )
+        Cipher::<Aes>::new(&self.key_generating_key, nonce)
+            .decrypt_in_place_detached(associated_data, buffer, tag)
+            .map_err(Into::into)
     }
 }
 
-/// AES-GCM-SIV: Misuse-Resistant Authenticated Encryption Cipher (RFC8452).
-struct Cipher<Aes>
+impl<Aes> AesGcmSiv<Aes>
 where
-    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt,
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
 {
-    /// Encryption cipher.
-    enc_cipher: Aes,
+    /// Begin encrypting or decrypting a message under the given nonce, with
+    /// associated data supplied incrementally via [`AadBuilder::add_aad`].
+    pub fn begin(&self, nonce: &Nonce) -> AadBuilder<Aes> {
+        AadBuilder::new(self, nonce)
+    }
 
-    /// POLYVAL universal hash.
-    polyval: Polyval,
+    /// Encrypt the given plaintext lazily, returning an iterator over
+    /// ciphertext chunks of `chunk_size` bytes (the last may be shorter)
+    /// followed by the authentication tag.
+    ///
+    /// The tag is computed eagerly; see [`EncryptChunks`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn encrypt_chunks<'msg>(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        plaintext: &'msg [u8],
+        chunk_size: usize,
+    ) -> Result<EncryptChunks<'msg, Aes>, GcmSivError> {
+        EncryptChunks::new(self, nonce, associated_data, plaintext, chunk_size)
+    }
 
-    /// Nonce.
-    nonce: Nonce,
-}
+    /// Encrypt the given plaintext payload into `out`, laid out according to
+    /// `options`, returning the authentication tag.
+    ///
+    /// `out` is cleared first unless [`EncryptOptions::append`] is set. With
+    /// the default options, `out` ends up holding exactly the output of
+    /// [`Aead::encrypt`][aead::Aead::encrypt].
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn encrypt_with<'msg, 'aad>(
+        &self,
+        nonce: &Nonce,
+        plaintext: impl Into<Payload<'msg, 'aad>>,
+        out: &mut Vec<u8>,
+        options: &EncryptOptions,
+    ) -> Result<Tag, GcmSivError> {
+        let payload = plaintext.into();
+        check_lengths(payload.msg.len() as u64, payload.aad.len() as u64)?;
 
-impl<Aes> Cipher<Aes>
-where
-    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
-{
-    /// Initialize AES-GCM-SIV, deriving per-nonce message-authentication and
-    /// message-encryption keys.
-    pub(crate) fn new(key_generating_key: &Aes, nonce: &Nonce) -> Self {
-        let mut mac_key = polyval::Key::default();
-        let mut enc_key = GenericArray::default();
-        let mut block = cipher::Block::<Aes>::default();
-        let mut counter = 0u32;
-
-        // Derive subkeys from the master key-generating-key in counter mode.
-        //
-        // From RFC8452 § 4: <https://tools.ietf.org/html/rfc8452#section-4>
-        //
-        // > The message-authentication key is 128 bit, and the message-encryption
-        // > key is either 128 (for AES-128) or 256 bit (for AES-256).
-        // >
-        // > These keys are generated by encrypting a series of plaintext blocks
-        // > that contain a 32-bit, little-endian counter followed by the nonce,
-        // > and then discarding the second half of the resulting ciphertext.  In
-        // > the AES-128 case, 128 + 128 = 256 bits of key material need to be
-        // > generated, and, since encrypting each block yields 64 bits after
-        // > discarding half, four blocks need to be encrypted.  The counter
-        // > values for these blocks are 0, 1, 2, and 3.  For AES-256, six blocks
-        // > are needed in total, with counter values 0 through 5 (inclusive).
-        for derived_key in &mut [mac_key.as_mut_slice(), enc_key.as_mut_slice()] {
-            for chunk in derived_key.chunks_mut(8) {
-                block[..4].copy_from_slice(&counter.to_le_bytes());
-                block[4..].copy_from_slice(nonce.as_slice());
-
-                key_generating_key.encrypt_block(&mut block);
-                chunk.copy_from_slice(&block.as_slice()[..8]);
-
-                counter += 1;
+        if !options.append {
+            out.clear();
+        }
+
+        let start = out.len();
+
+        if options.tag_position == TagPosition::Prefix {
+            out.extend_from_slice(&Tag::default());
+        }
+
+        let msg_start = out.len();
+        out.extend_from_slice(payload.msg);
+
+        let tag = match Cipher::<Aes>::new(&self.key_generating_key, nonce)
+            .encrypt_in_place_detached(payload.aad, &mut out[msg_start..])
+        {
+            Ok(tag) => tag,
+            Err(err) => {
+                out.truncate(start);
+                return Err(err);
             }
+        };
+
+        match options.tag_position {
+            TagPosition::Suffix => out.extend_from_slice(&tag),
+            TagPosition::Prefix => out[start..msg_start].copy_from_slice(&tag),
+            TagPosition::Detached => (),
         }
 
-        let result = Self {
-            enc_cipher: Aes::new(&enc_key),
-            polyval: Polyval::new(&mac_key),
-            nonce: *nonce,
+        Ok(tag)
+    }
+
+    /// Decrypt the given ciphertext payload, laid out according to `options`,
+    /// into `out`.
+    ///
+    /// `tag` must be given if and only if the options specify
+    /// [`TagPosition::Detached`], otherwise [`GcmSivError::TagPlacement`] is
+    /// returned. `out` is cleared first unless [`EncryptOptions::append`] is
+    /// set. If the ciphertext fails to authenticate, `out` is truncated back
+    /// to its original length after applying the [`AuthFailurePolicy`], so
+    /// the policy only controls what is left behind in its spare capacity.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn decrypt_with<'msg, 'aad>(
+        &self,
+        nonce: &Nonce,
+        ciphertext: impl Into<Payload<'msg, 'aad>>,
+        tag: Option<&Tag>,
+        out: &mut Vec<u8>,
+        options: &EncryptOptions,
+    ) -> Result<(), GcmSivError> {
+        let payload = ciphertext.into();
+        let tag_size = U16::to_usize();
+
+        let (ciphertext, tag) = match (options.tag_position, tag) {
+            (TagPosition::Suffix, None) => {
+                let tag_pos = payload
+                    .msg
+                    .len()
+                    .checked_sub(tag_size)
+                    .ok_or(GcmSivError::CiphertextTooShort)?;
+
+                let (ciphertext, tag) = payload.msg.split_at(tag_pos);
+                (ciphertext, Tag::from_slice(tag))
+            }
+            (TagPosition::Prefix, None) => {
+                if payload.msg.len() < tag_size {
+                    return Err(GcmSivError::CiphertextTooShort);
+                }
+
+                let (tag, ciphertext) = payload.msg.split_at(tag_size);
+                (ciphertext, Tag::from_slice(tag))
+            }
+            (TagPosition::Detached, Some(tag)) => (payload.msg, tag),
+            _ => return Err(GcmSivError::TagPlacement),
         };
 
-        // Zeroize all intermediate buffers
-        // TODO(tarcieri): use `Zeroizing` when const generics land
-        mac_key.as_mut_slice().zeroize();
-        enc_key.as_mut_slice().zeroize();
-        block.as_mut_slice().zeroize();
+        check_ciphertext_lengths(ciphertext.len() as u64, payload.aad.len() as u64)?;
+
+        if !options.append {
+            out.clear();
+        }
+
+        let start = out.len();
+        out.extend_from_slice(ciphertext);
+
+        let result = self.decrypt_in_place_detached_with(
+            nonce,
+            payload.aad,
+            &mut out[start..],
+            tag,
+            options.auth_failure,
+        );
+
+        if result.is_err() {
+            out.truncate(start);
+        }
 
         result
     }
 
-    /// Encrypt the given message in-place, returning the authentication tag.
-    pub(crate) fn encrypt_in_place_detached(
-        mut self,
+    /// Decrypt the given buffer in-place, authenticating it against a
+    /// separately provided tag, with `policy` determining what is left in
+    /// `buffer` if it fails to authenticate.
+    ///
+    /// [`AeadInPlace::decrypt_in_place_detached`] always uses
+    /// [`AuthFailurePolicy::Reencrypt`].
+    pub fn decrypt_in_place_detached_with(
+        &self,
+        nonce: &Nonce,
         associated_data: &[u8],
         buffer: &mut [u8],
-    ) -> Result<Tag, Error> {
-        if buffer.len() as u64 > P_MAX || associated_data.len() as u64 > A_MAX {
-            return Err(Error);
+        tag: &Tag,
+        policy: AuthFailurePolicy,
+    ) -> Result<(), GcmSivError> {
+        let mut cipher = Cipher::<Aes>::new(&self.key_generating_key, nonce);
+        cipher.reencrypt_on_failure = policy == AuthFailurePolicy::Reencrypt;
+        let result = cipher.decrypt_in_place_detached(associated_data, buffer, tag);
+
+        if result.is_err() && policy == AuthFailurePolicy::Zero {
+            buffer.fill(0);
         }
 
-        self.polyval.update_padded(associated_data);
-        self.polyval.update_padded(buffer);
+        result
+    }
 
-        let tag = self.finish_tag(associated_data.len(), buffer.len());
-        init_ctr(&self.enc_cipher, &tag).apply_keystream_partial(buffer.into());
+    /// Encrypt the given plaintext payload, returning the authentication tag
+    /// followed by the ciphertext (`tag || ciphertext`).
+    ///
+    /// This is [`AesGcmSiv::encrypt_with`] using [`TagPosition::Prefix`].
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn encrypt_prefixed<'msg, 'aad>(
+        &self,
+        nonce: &Nonce,
+        plaintext: impl Into<Payload<'msg, 'aad>>,
+    ) -> Result<Vec<u8>, GcmSivError> {
+        let options = EncryptOptions::new().tag_position(TagPosition::Prefix);
+        let mut buffer = Vec::new();
+        self.encrypt_with(nonce, plaintext, &mut buffer, &options)?;
+        Ok(buffer)
+    }
 
-        Ok(tag)
+    /// Decrypt the given ciphertext payload, laid out as
+    /// `tag || ciphertext`, returning the plaintext.
+    ///
+    /// This is [`AesGcmSiv::decrypt_with`] using [`TagPosition::Prefix`].
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn decrypt_prefixed<'msg, 'aad>(
+        &self,
+        nonce: &Nonce,
+        ciphertext: impl Into<Payload<'msg, 'aad>>,
+    ) -> Result<Vec<u8>, GcmSivError> {
+        let options = EncryptOptions::new().tag_position(TagPosition::Prefix);
+        let mut buffer = Vec::new();
+        self.decrypt_with(nonce, ciphertext, None, &mut buffer, &options)?;
+        Ok(buffer)
     }
 
-    /// Decrypt the given message, first authenticating ciphertext integrity
-    /// and returning an error if it's been tampered with.
-    pub(crate) fn decrypt_in_place_detached(
-        mut self,
-        associated_data: &[u8],
-        buffer: &mut [u8],
+    /// Encrypt the given plaintext payload, returning the ciphertext and the
+    /// authentication tag as separate values rather than appending the tag
+    /// to the ciphertext.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn encrypt_detached<'msg, 'aad>(
+        &self,
+        nonce: &Nonce,
+        plaintext: impl Into<Payload<'msg, 'aad>>,
+    ) -> Result<(Vec<u8>, Tag), GcmSivError> {
+        let payload = plaintext.into();
+
+        // Check lengths before allocating
+        check_lengths(payload.msg.len() as u64, payload.aad.len() as u64)?;
+
+        let mut buffer = payload.msg.to_vec();
+        let tag = Cipher::<Aes>::new(&self.key_generating_key, nonce)
+            .encrypt_in_place_detached(payload.aad, &mut buffer)?;
+
+        Ok((buffer, tag))
+    }
+
+    /// Decrypt the given ciphertext payload, authenticating it against a
+    /// separately provided tag.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn decrypt_detached<'msg, 'aad>(
+        &self,
+        nonce: &Nonce,
+        ciphertext: impl Into<Payload<'msg, 'aad>>,
         tag: &Tag,
-    ) -> Result<(), Error> {
-        if buffer.len() as u64 > C_MAX || associated_data.len() as u64 > A_MAX {
-            return Err(Error);
-        }
+    ) -> Result<Vec<u8>, GcmSivError> {
+        let payload = ciphertext.into();
 
-        self.polyval.update_padded(associated_data);
+        // Check lengths before allocating
+        check_ciphertext_lengths(payload.msg.len() as u64, payload.aad.len() as u64)?;
 
-        // TODO(tarcieri): interleave decryption and authentication
-        init_ctr(&self.enc_cipher, tag).apply_keystream_partial(buffer.into());
-        self.polyval.update_padded(buffer);
+        let mut buffer = payload.msg.to_vec();
+        Cipher::<Aes>::new(&self.key_generating_key, nonce).decrypt_in_place_detached(
+            payload.aad,
+            &mut buffer,
+            tag,
+        )?;
+
+        Ok(buffer)
+    }
 
-        let expected_tag = self.finish_tag(associated_data.len(), buffer.len());
+    /// Encrypt the given plaintext payload, returning the ciphertext with the
+    /// tag appended, or [`GcmSivError::AllocationFailed`] instead of aborting
+    /// if the output buffer can't be allocated.
+    ///
+    /// Lengths are checked against [`P_MAX`] and [`A_MAX`] before allocating.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn try_encrypt<'msg, 'aad>(
+        &self,
+        nonce: &Nonce,
+        plaintext: impl Into<Payload<'msg, 'aad>>,
+    ) -> Result<Vec<u8>, GcmSivError> {
+        let payload = plaintext.into();
+        check_lengths(payload.msg.len() as u64, payload.aad.len() as u64)?;
 
-        use subtle::ConstantTimeEq;
-        if expected_tag.ct_eq(tag).into() {
-            Ok(())
-        } else {
-            // On MAC verify failure, re-encrypt the plaintext buffer to
-            // prevent accidental exposure.
-            init_ctr(&self.enc_cipher, tag).apply_keystream_partial(buffer.into());
-            Err(Error)
+        let buffer_len = payload
+            .msg
+            .len()
+            .checked_add(U16::to_usize())
+            .ok_or(GcmSivError::AllocationFailed)?;
+
+        let mut buffer = Vec::new();
+        buffer
+            .try_reserve_exact(buffer_len)
+            .map_err(|_| GcmSivError::AllocationFailed)?;
+        buffer.extend_from_slice(payload.msg);
+
+        let tag = Cipher::<Aes>::new(&self.key_generating_key, nonce)
+            .encrypt_in_place_detached(payload.aad, &mut buffer)?;
+
+        buffer.extend_from_slice(&tag);
+        Ok(buffer)
+    }
+
+    /// Decrypt the given ciphertext payload (with the tag appended),
+    /// returning [`GcmSivError::AllocationFailed`] instead of aborting if the
+    /// plaintext buffer can't be allocated.
+    ///
+    /// Lengths are checked against [`C_MAX`] and [`A_MAX`] before allocating.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn try_decrypt<'msg, 'aad>(
+        &self,
+        nonce: &Nonce,
+        ciphertext: impl Into<Payload<'msg, 'aad>>,
+    ) -> Result<Vec<u8>, GcmSivError> {
+        let payload = ciphertext.into();
+        let tag_pos = payload
+            .msg
+            .len()
+            .checked_sub(U16::to_usize())
+            .ok_or(GcmSivError::CiphertextTooShort)?;
+
+        let (ciphertext, tag) = payload.msg.split_at(tag_pos);
+        check_ciphertext_lengths(ciphertext.len() as u64, payload.aad.len() as u64)?;
+
+        let mut buffer = Vec::new();
+        buffer
+            .try_reserve_exact(ciphertext.len())
+            .map_err(|_| GcmSivError::AllocationFailed)?;
+        buffer.extend_from_slice(ciphertext);
+
+        Cipher::<Aes>::new(&self.key_generating_key, nonce).decrypt_in_place_detached(
+            payload.aad,
+            &mut buffer,
+            Tag::from_slice(tag),
+        )?;
+
+        Ok(buffer)
+    }
+
+    /// Decrypt the given ciphertext payload (with the tag appended),
+    /// returning the plaintext in a buffer which is zeroized on drop.
+    ///
+    /// The buffer is allocated at its final size up front, so no copies of
+    /// the plaintext are left behind by reallocation.
+    #[cfg(all(feature = "alloc", feature = "zeroize"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "zeroize"))))]
+    pub fn decrypt_zeroizing<'msg, 'aad>(
+        &self,
+        nonce: &Nonce,
+        ciphertext: impl Into<Payload<'msg, 'aad>>,
+    ) -> Result<zeroize::Zeroizing<Vec<u8>>, GcmSivError> {
+        let payload = ciphertext.into();
+        let tag_pos = payload
+            .msg
+            .len()
+            .checked_sub(U16::to_usize())
+            .ok_or(GcmSivError::CiphertextTooShort)?;
+
+        let (ciphertext, tag) = payload.msg.split_at(tag_pos);
+        check_ciphertext_lengths(ciphertext.len() as u64, payload.aad.len() as u64)?;
+
+        let mut buffer = zeroize::Zeroizing::new(Vec::with_capacity(ciphertext.len()));
+        buffer.extend_from_slice(ciphertext);
+
+        Cipher::<Aes>::new(&self.key_generating_key, nonce).decrypt_in_place_detached(
+            payload.aad,
+            &mut buffer,
+            Tag::from_slice(tag),
+        )?;
+
+        Ok(buffer)
+    }
+
+    /// Encrypt the given plaintext into `out`, writing the ciphertext followed
+    /// by the authentication tag and returning the number of bytes written.
+    ///
+    /// `out` must be at least `plaintext.len() + 16` bytes long.
+    pub fn encrypt_to_slice(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        plaintext: &[u8],
+        out: &mut [u8],
+    ) -> Result<usize, GcmSivError> {
+        self.encrypt_vectored_to_slice(nonce, associated_data, &[plaintext], out)
+    }
+
+    /// Encrypt a plaintext split across several slices into `out`, writing
+    /// the ciphertext followed by the authentication tag and returning the
+    /// number of bytes written.
+    ///
+    /// The result is identical to [`AesGcmSiv::encrypt_to_slice`] with the
+    /// slices of `plaintext` concatenated. `out` must be large enough to hold
+    /// all of them plus the 16-byte tag.
+    pub fn encrypt_vectored_to_slice(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        plaintext: &[&[u8]],
+        out: &mut [u8],
+    ) -> Result<usize, GcmSivError> {
+        let tag_pos = plaintext
+            .iter()
+            .try_fold(0usize, |len, slice| len.checked_add(slice.len()))
+            .ok_or(GcmSivError::PlaintextTooLong)?;
+
+        // Check lengths before copying the plaintext into `out`
+        check_lengths(tag_pos as u64, associated_data.len() as u64)?;
+
+        let out_len = tag_pos
+            .checked_add(U16::to_usize())
+            .ok_or(GcmSivError::PlaintextTooLong)?;
+
+        if out.len() < out_len {
+            return Err(GcmSivError::BufferTooSmall);
+        }
+
+        let (buffer, tag_out) = out[..out_len].split_at_mut(tag_pos);
+        let mut pos = 0;
+
+        for slice in plaintext {
+            buffer[pos..][..slice.len()].copy_from_slice(slice);
+            pos += slice.len();
         }
+
+        let tag = Cipher::<Aes>::new(&self.key_generating_key, nonce)
+            .encrypt_in_place_detached(associated_data, buffer)?;
+
+        tag_out.copy_from_slice(&tag);
+        Ok(out_len)
     }
 
-    /// Finish computing POLYVAL tag for AAD and buffer of the given length.
-    fn finish_tag(&mut self, associated_data_len: usize, buffer_len: usize) -> Tag {
-        let associated_data_bits = (associated_data_len as u64) * 8;
-        let buffer_bits = (buffer_len as u64) * 8;
+    /// Decrypt the given ciphertext (with the tag appended) into `out`,
+    /// returning the length of the plaintext.
+    ///
+    /// `out` must be at least `ciphertext.len() - 16` bytes long. If the
+    /// ciphertext fails to authenticate, the plaintext region of `out` is
+    /// zeroed.
+    pub fn decrypt_to_slice(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        ciphertext: &[u8],
+        out: &mut [u8],
+    ) -> Result<usize, GcmSivError> {
+        let tag_pos = ciphertext
+            .len()
+            .checked_sub(U16::to_usize())
+            .ok_or(GcmSivError::CiphertextTooShort)?;
 
-        let mut block = polyval::Block::default();
-        block[..8].copy_from_slice(&associated_data_bits.to_le_bytes());
-        block[8..].copy_from_slice(&buffer_bits.to_le_bytes());
-        self.polyval.update(&[block]);
+        if out.len() < tag_pos {
+            return Err(GcmSivError::BufferTooSmall);
+        }
 
-        let mut tag = self.polyval.finalize_reset();
+        let (ciphertext, tag) = ciphertext.split_at(tag_pos);
+        let buffer = &mut out[..tag_pos];
+        buffer.copy_from_slice(ciphertext);
 
-        // XOR the nonce into the resulting tag
-        for (i, byte) in tag[..12].iter_mut().enumerate() {
-            *byte ^= self.nonce[i];
+        let result = Cipher::<Aes>::new(&self.key_generating_key, nonce).decrypt_in_place_detached(
+            associated_data,
+            buffer,
+            Tag::from_slice(tag),
+        );
+
+        if result.is_err() {
+            buffer.fill(0);
         }
 
-        // Clear the highest bit
-        tag[15] &= 0x7f;
+        result.map(|()| tag_pos)
+    }
 
-        self.enc_cipher.encrypt_block(&mut tag);
-        tag
+    /// Encrypt the given plaintext into a stack-allocated array, returning
+    /// the array and the number of bytes of ciphertext and tag written to it.
+    ///
+    /// Returns [`GcmSivError::BufferTooSmall`] if `plaintext.len() + 16`
+    /// exceeds `N`.
+    pub fn encrypt_array<const N: usize>(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        plaintext: &[u8],
+    ) -> Result<([u8; N], usize), GcmSivError> {
+        let mut out = [0u8; N];
+        let len = self.encrypt_to_slice(nonce, associated_data, plaintext, &mut out)?;
+        Ok((out, len))
     }
-}
 
-/// Initialize counter mode.
-///
-/// From RFC8452 § 4: <https://tools.ietf.org/html/rfc8452#section-4>
-///
-/// > The initial counter block is the tag with the most significant bit
-/// > of the last byte set to one.
-#[inline]
+    /// Decrypt the given ciphertext (with the tag appended) into a
+    /// stack-allocated array, returning the array and the length of the
+    /// plaintext written to it.
+    ///
+    /// Returns [`GcmSivError::BufferTooSmall`] if `ciphertext.len() - 16`
+    /// exceeds `N`.
+    pub fn decrypt_array<const N: usize>(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<([u8; N], usize), GcmSivError> {
+        let mut out = [0u8; N];
+        let len = self.decrypt_to_slice(nonce, associated_data, ciphertext, &mut out)?;
+        Ok((out, len))
+    }
+
+    /// Encrypt the given plaintext into possibly-uninitialized memory,
+    /// returning the initialized prefix of `out` holding the ciphertext
+    /// followed by the authentication tag.
+    ///
+    /// `out` must be at least `plaintext.len() + 16` bytes long. Every byte of
+    /// the returned slice is written exactly once, and `out` is never read.
+    pub fn encrypt_to_uninit<'out>(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        plaintext: &[u8],
+        out: &'out mut [MaybeUninit<u8>],
+    ) -> Result<&'out mut [u8], GcmSivError> {
+        Cipher::<Aes>::new(&self.key_generating_key, nonce).encrypt_to_uninit(
+            associated_data,
+            plaintext,
+            out,
+        )
+    }
+
+    /// Decrypt the given ciphertext (with the tag appended) into
+    /// possibly-uninitialized memory, returning the initialized prefix of
+    /// `out` holding the plaintext.
+    ///
+    /// `out` must be at least `ciphertext.len() - 16` bytes long. If the
+    /// ciphertext fails to authenticate, the plaintext region of `out` is
+    /// zeroed.
+    pub fn decrypt_to_uninit<'out>(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        ciphertext: &[u8],
+        out: &'out mut [MaybeUninit<u8>],
+    ) -> Result<&'out mut [u8], GcmSivError> {
+        let tag_pos = ciphertext
+            .len()
+            .checked_sub(U16::to_usize())
+            .ok_or(GcmSivError::CiphertextTooShort)?;
+
+        let (ciphertext, tag) = ciphertext.split_at(tag_pos);
+        Cipher::<Aes>::new(&self.key_generating_key, nonce).decrypt_to_uninit(
+            associated_data,
+            ciphertext,
+            Tag::from_slice(tag),
+            out,
+        )
+    }
+
+    /// Compute an authentication tag over the given associated data alone.
+    ///
+    /// This is equivalent to encrypting an empty plaintext.
+    pub fn authenticate(&self, nonce: &Nonce, associated_data: &[u8]) -> Result<Tag, GcmSivError> {
+        Cipher::<Aes>::new(&self.key_generating_key, nonce)
+            .encrypt_in_place_detached(associated_data, &mut [])
+    }
+
+    /// Derive the per-nonce message-authentication (POLYVAL) key and
+    /// message-encryption key, as described in RFC 8452 § 4.
+    ///
+    /// # ⚠️ Warning: Hazmat!
+    ///
+    /// Either subkey is sufficient to forge or decrypt messages under this
+    /// nonce. This is intended only for comparing intermediate values
+    /// against test vectors, e.g. those in RFC 8452 Appendix C.
+    #[cfg(feature = "hazmat")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hazmat")))]
+    pub fn derive_subkeys(&self, nonce: &Nonce) -> (polyval::Key, Key<Aes>) {
+        let mut mac_key = polyval::Key::default();
+        let mut enc_key = GenericArray::default();
+        derive_subkeys(&self.key_generating_key, nonce, &mut mac_key, &mut enc_key);
+        (mac_key, enc_key)
+    }
+
+    /// Compute the authentication tag for the given plaintext and associated
+    /// data, without encrypting the plaintext.
+    ///
+    /// This is the tag [`AeadInPlace::encrypt_in_place_detached`] would
+    /// return, and is useful for checking the POLYVAL pass separately from
+    /// the CTR pass, e.g. against known-answer tests.
+    pub fn compute_tag(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Tag, GcmSivError> {
+        Cipher::<Aes>::new(&self.key_generating_key, nonce).compute_tag(associated_data, plaintext)
+    }
+
+    /// Derive the subkeys for the given nonce once, for running several
+    /// operations on the same message.
+    ///
+    /// See [`PerNonceKeys`] for when this is (and isn't) safe to use.
+    pub fn per_nonce_keys(&self, nonce: &Nonce) -> PerNonceKeys<Aes>
+    where
+        Aes: Clone,
+    {
+        PerNonceKeys::new(self, nonce)
+    }
+
+    /// Verify a tag computed by [`AesGcmSiv::authenticate`] over the given
+    /// associated data.
+    pub fn verify_tag(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        tag: &Tag,
+    ) -> Result<(), GcmSivError> {
+        self.verify_detached(nonce, associated_data, &[], tag)
+    }
+
+    /// Encrypt the given buffer in-place with associated data split across
+    /// several slices, returning the authentication tag.
+    ///
+    /// `associated_data` may be any iterator over byte slices, e.g. an array
+    /// of `&[u8]` or the result of [`slice::chunks`]. The result is identical
+    /// to [`AeadInPlace::encrypt_in_place_detached`] with the slices
+    /// concatenated, without having to concatenate them into a temporary
+    /// buffer.
+    pub fn encrypt_in_place_detached_vectored<I>(
+        &self,
+        nonce: &Nonce,
+        associated_data: I,
+        buffer: &mut [u8],
+    ) -> Result<Tag, GcmSivError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        Cipher::<Aes>::new(&self.key_generating_key, nonce)
+            .encrypt_in_place_detached_vectored(associated_data, buffer)
+    }
+
+    /// Decrypt the given buffer in-place with associated data split across
+    /// several slices, authenticating it against a separately provided tag.
+    pub fn decrypt_in_place_detached_vectored<I>(
+        &self,
+        nonce: &Nonce,
+        associated_data: I,
+        buffer: &mut [u8],
+        tag: &Tag,
+    ) -> Result<(), GcmSivError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        Cipher::<Aes>::new(&self.key_generating_key, nonce).decrypt_in_place_detached_vectored(
+            associated_data,
+            buffer,
+            tag,
+        )
+    }
+
+    /// Encrypt a message split across two buffers in-place (e.g. the two
+    /// halves of a ring buffer), returning the authentication tag.
+    ///
+    /// The result is identical to encrypting `head || tail` as a single
+    /// contiguous buffer.
+    pub fn encrypt_in_place_split_detached(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        head: &mut [u8],
+        tail: &mut [u8],
+    ) -> Result<Tag, GcmSivError> {
+        Cipher::<Aes>::new(&self.key_generating_key, nonce).encrypt_in_place_split_detached(
+            associated_data,
+            head,
+            tail,
+        )
+    }
+
+    /// Decrypt a message split across two buffers in-place, authenticating
+    /// `head || tail` against a separately provided tag.
+    ///
+    /// On authentication failure both buffers are restored to their original
+    /// ciphertext.
+    pub fn decrypt_in_place_split_detached(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        head: &mut [u8],
+        tail: &mut [u8],
+        tag: &Tag,
+    ) -> Result<(), GcmSivError> {
+        Cipher::<Aes>::new(&self.key_generating_key, nonce).decrypt_in_place_split_detached(
+            associated_data,
+            head,
+            tail,
+            tag,
+        )
+    }
+
+    /// Authenticate the given ciphertext (with the tag appended) without
+    /// exposing the resulting plaintext.
+    ///
+    /// Accepts exactly the same inputs as [`AeadInPlace::decrypt_in_place`],
+    /// but never writes plaintext into caller-visible memory.
+    ///
+    /// Note that this still performs a full decryption internally: the tag
+    /// is computed over the plaintext, so verifying costs as much as
+    /// decrypting. Plaintext is produced one block at a time into a scratch
+    /// block, which is zeroized afterwards when the `zeroize` feature is
+    /// enabled.
+    pub fn verify(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<(), GcmSivError> {
+        let tag_pos = ciphertext
+            .len()
+            .checked_sub(U16::to_usize())
+            .ok_or(GcmSivError::CiphertextTooShort)?;
+
+        let (ciphertext, tag) = ciphertext.split_at(tag_pos);
+        self.verify_detached(nonce, associated_data, ciphertext, Tag::from_slice(tag))
+    }
+
+    /// Authenticate the given ciphertext against a separately provided tag
+    /// without exposing the resulting plaintext.
+    ///
+    /// See [`AesGcmSiv::verify`] regarding the cost of verification.
+    pub fn verify_detached(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        ciphertext: &[u8],
+        tag: &Tag,
+    ) -> Result<(), GcmSivError> {
+        Cipher::<Aes>::new(&self.key_generating_key, nonce).verify_detached(
+            associated_data,
+            ciphertext,
+            tag,
+        )
+    }
+}
+
+/// AES-GCM-SIV: Misuse-Resistant Authenticated Encryption Cipher (RFC8452).
+///
+/// With the `zeroize` feature, the per-message subkeys held by `enc_cipher`
+/// and `polyval` are wiped when this is dropped.
+#[derive(Clone)]
+struct Cipher<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt,
+{
+    /// Encryption cipher.
+    enc_cipher: Aes,
+
+    /// POLYVAL universal hash.
+    polyval: Polyval,
+
+    /// Nonce.
+    nonce: Nonce,
+
+    /// Re-encrypt the buffer when decryption fails to authenticate.
+    reencrypt_on_failure: bool,
+}
+
+impl<Aes> Cipher<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+    /// Initialize AES-GCM-SIV, deriving per-nonce message-authentication and
+    /// message-encryption keys.
+    pub(crate) fn new(key_generating_key: &Aes, nonce: &Nonce) -> Self {
+        let mut mac_key = polyval::Key::default();
+        let mut enc_key = GenericArray::default();
+        derive_subkeys(key_generating_key, nonce, &mut mac_key, &mut enc_key);
+
+        let result = Self {
+            enc_cipher: Aes::new(&enc_key),
+            polyval: Polyval::new(&mac_key),
+            nonce: *nonce,
+            reencrypt_on_failure: true,
+        };
+
+        // Zeroize all intermediate buffers (the scratch block is wiped by
+        // `derive_subkeys`, and its counter isn't secret)
+        // TODO(tarcieri): use `Zeroizing` when const generics land
+        #[cfg(feature = "zeroize")]
+        {
+            mac_key.as_mut_slice().zeroize();
+            enc_key.as_mut_slice().zeroize();
+        }
+
+        result
+    }
+
+    /// Encrypt the given message in-place, returning the authentication tag.
+    pub(crate) fn encrypt_in_place_detached(
+        mut self,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<Tag, GcmSivError> {
+        check_lengths(buffer.len() as u64, associated_data.len() as u64)?;
+        self.polyval.update_padded(associated_data);
+        self.encrypt_message(associated_data.len() as u64, buffer)
+    }
+
+    /// Encrypt the given message in-place with associated data split across
+    /// several slices, returning the authentication tag.
+    pub(crate) fn encrypt_in_place_detached_vectored<I>(
+        mut self,
+        associated_data: I,
+        buffer: &mut [u8],
+    ) -> Result<Tag, GcmSivError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let associated_data_len = self.update_aad_vectored(associated_data)?;
+        self.encrypt_message(associated_data_len, buffer)
+    }
+
+    /// Decrypt the given message, first authenticating ciphertext integrity
+    /// and returning an error if it's been tampered with.
+    pub(crate) fn decrypt_in_place_detached(
+        mut self,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &Tag,
+    ) -> Result<(), GcmSivError> {
+        check_ciphertext_lengths(buffer.len() as u64, associated_data.len() as u64)?;
+        self.polyval.update_padded(associated_data);
+        self.decrypt_message(associated_data.len() as u64, buffer, tag)
+    }
+
+    /// Decrypt the given message with associated data split across several
+    /// slices, first authenticating ciphertext integrity.
+    pub(crate) fn decrypt_in_place_detached_vectored<I>(
+        mut self,
+        associated_data: I,
+        buffer: &mut [u8],
+        tag: &Tag,
+    ) -> Result<(), GcmSivError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let associated_data_len = self.update_aad_vectored(associated_data)?;
+        self.decrypt_message(associated_data_len, buffer, tag)
+    }
+
+    /// Encrypt a message split across two buffers in-place, returning the
+    /// authentication tag.
+    pub(crate) fn encrypt_in_place_split_detached(
+        mut self,
+        associated_data: &[u8],
+        head: &mut [u8],
+        tail: &mut [u8],
+    ) -> Result<Tag, GcmSivError> {
+        let buffer_len = head.len() as u64 + tail.len() as u64;
+        check_lengths(buffer_len, associated_data.len() as u64)?;
+
+        self.polyval.update_padded(associated_data);
+
+        let mut input = PaddedInput::default();
+        input.update(&mut self.polyval, head);
+        input.update(&mut self.polyval, tail);
+        input.finish(&mut self.polyval);
+
+        let tag = self.finish_tag(associated_data.len() as u64, buffer_len);
+
+        let mut keystream = Keystream::new(init_ctr(&self.enc_cipher, &tag));
+        keystream.apply(head);
+        keystream.apply(tail);
+
+        Ok(tag)
+    }
+
+    /// Decrypt a message split across two buffers in-place, first
+    /// authenticating ciphertext integrity.
+    pub(crate) fn decrypt_in_place_split_detached(
+        mut self,
+        associated_data: &[u8],
+        head: &mut [u8],
+        tail: &mut [u8],
+        tag: &Tag,
+    ) -> Result<(), GcmSivError> {
+        let buffer_len = head.len() as u64 + tail.len() as u64;
+        check_ciphertext_lengths(buffer_len, associated_data.len() as u64)?;
+
+        self.polyval.update_padded(associated_data);
+
+        {
+            let mut keystream = Keystream::new(init_ctr(&self.enc_cipher, tag));
+            keystream.apply(head);
+            keystream.apply(tail);
+        }
+
+        let mut input = PaddedInput::default();
+        input.update(&mut self.polyval, head);
+        input.update(&mut self.polyval, tail);
+        input.finish(&mut self.polyval);
+
+        let expected_tag = self.finish_tag(associated_data.len() as u64, buffer_len);
+
+        use subtle::ConstantTimeEq;
+        if expected_tag.ct_eq(tag).into() {
+            Ok(())
+        } else {
+            // On MAC verify failure, re-encrypt the plaintext buffers to
+            // prevent accidental exposure.
+            let mut keystream = Keystream::new(init_ctr(&self.enc_cipher, tag));
+            keystream.apply(head);
+            keystream.apply(tail);
+            Err(GcmSivError::AuthFailed)
+        }
+    }
+
+    /// Encrypt the given message into possibly-uninitialized memory,
+    /// appending the authentication tag.
+    pub(crate) fn encrypt_to_uninit<'out>(
+        mut self,
+        associated_data: &[u8],
+        plaintext: &[u8],
+        out: &'out mut [MaybeUninit<u8>],
+    ) -> Result<&'out mut [u8], GcmSivError> {
+        check_lengths(plaintext.len() as u64, associated_data.len() as u64)?;
+
+        let out_len = plaintext
+            .len()
+            .checked_add(U16::to_usize())
+            .ok_or(GcmSivError::PlaintextTooLong)?;
+
+        let out = out.get_mut(..out_len).ok_or(GcmSivError::BufferTooSmall)?;
+
+        self.polyval.update_padded(associated_data);
+        self.polyval.update_padded(plaintext);
+
+        let tag = self.finish_tag(associated_data.len() as u64, plaintext.len() as u64);
+
+        let (ciphertext_out, tag_out) = out.split_at_mut(plaintext.len());
+        apply_keystream_uninit(init_ctr(&self.enc_cipher, &tag), plaintext, ciphertext_out);
+
+        for (slot, byte) in tag_out.iter_mut().zip(tag.iter()) {
+            *slot = MaybeUninit::new(*byte);
+        }
+
+        // SAFETY: every byte of `out` was written above
+        Ok(unsafe { assume_init_mut(out) })
+    }
+
+    /// Decrypt the given message into possibly-uninitialized memory, then
+    /// authenticate the resulting plaintext.
+    pub(crate) fn decrypt_to_uninit<'out>(
+        mut self,
+        associated_data: &[u8],
+        ciphertext: &[u8],
+        tag: &Tag,
+        out: &'out mut [MaybeUninit<u8>],
+    ) -> Result<&'out mut [u8], GcmSivError> {
+        check_ciphertext_lengths(ciphertext.len() as u64, associated_data.len() as u64)?;
+
+        let out = out
+            .get_mut(..ciphertext.len())
+            .ok_or(GcmSivError::BufferTooSmall)?;
+
+        apply_keystream_uninit(init_ctr(&self.enc_cipher, tag), ciphertext, out);
+
+        // SAFETY: every byte of `out` was written by `apply_keystream_uninit`
+        let plaintext = unsafe { assume_init_mut(out) };
+
+        self.polyval.update_padded(associated_data);
+        self.polyval.update_padded(plaintext);
+
+        let expected_tag = self.finish_tag(associated_data.len() as u64, plaintext.len() as u64);
+
+        use subtle::ConstantTimeEq;
+        if expected_tag.ct_eq(tag).into() {
+            Ok(plaintext)
+        } else {
+            plaintext.fill(0);
+            Err(GcmSivError::AuthFailed)
+        }
+    }
+
+    /// Compute the authentication tag for the given message without
+    /// encrypting it.
+    pub(crate) fn compute_tag(
+        mut self,
+        associated_data: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Tag, GcmSivError> {
+        check_lengths(plaintext.len() as u64, associated_data.len() as u64)?;
+        self.polyval.update_padded(associated_data);
+        self.polyval.update_padded(plaintext);
+        Ok(self.finish_tag(associated_data.len() as u64, plaintext.len() as u64))
+    }
+
+    /// Authenticate the given ciphertext without producing any plaintext.
+    pub(crate) fn verify_detached(
+        mut self,
+        associated_data: &[u8],
+        ciphertext: &[u8],
+        tag: &Tag,
+    ) -> Result<(), GcmSivError> {
+        check_ciphertext_lengths(ciphertext.len() as u64, associated_data.len() as u64)?;
+        self.polyval.update_padded(associated_data);
+        self.verify_message(associated_data.len() as u64, ciphertext, tag)
+    }
+
+    /// Absorb associated data split across several slices into POLYVAL as if
+    /// the slices were concatenated, returning its total length.
+    fn update_aad_vectored<I>(&mut self, associated_data: I) -> Result<u64, GcmSivError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let mut input = PaddedInput::default();
+
+        for slice in associated_data {
+            input.update(&mut self.polyval, slice.as_ref());
+        }
+
+        let associated_data_len = input.finish(&mut self.polyval);
+
+        if associated_data_len > A_MAX {
+            return Err(GcmSivError::AadTooLong);
+        }
+
+        Ok(associated_data_len)
+    }
+
+    /// Encrypt the message in-place after associated data of the given
+    /// length has been absorbed, returning the authentication tag.
+    fn encrypt_message(
+        &mut self,
+        associated_data_len: u64,
+        buffer: &mut [u8],
+    ) -> Result<Tag, GcmSivError> {
+        check_lengths(buffer.len() as u64, associated_data_len)?;
+
+        self.polyval.update_padded(buffer);
+
+        let tag = self.finish_tag(associated_data_len, buffer.len() as u64);
+        init_ctr(&self.enc_cipher, &tag).apply_keystream_partial(buffer.into());
+
+        Ok(tag)
+    }
+
+    /// Decrypt the message in-place after associated data of the given
+    /// length has been absorbed, authenticating it against `tag`.
+    fn decrypt_message(
+        &mut self,
+        associated_data_len: u64,
+        buffer: &mut [u8],
+        tag: &Tag,
+    ) -> Result<(), GcmSivError> {
+        check_ciphertext_lengths(buffer.len() as u64, associated_data_len)?;
+
+        // TODO(tarcieri): interleave decryption and authentication
+        init_ctr(&self.enc_cipher, tag).apply_keystream_partial(buffer.into());
+        self.polyval.update_padded(buffer);
+
+        let expected_tag = self.finish_tag(associated_data_len, buffer.len() as u64);
+
+        use subtle::ConstantTimeEq;
+        if expected_tag.ct_eq(tag).into() {
+            Ok(())
+        } else {
+            // On MAC verify failure, re-encrypt the plaintext buffer to
+            // prevent accidental exposure.
+            if self.reencrypt_on_failure {
+                init_ctr(&self.enc_cipher, tag).apply_keystream_partial(buffer.into());
+            }
+            Err(GcmSivError::AuthFailed)
+        }
+    }
+
+    /// Authenticate the message after associated data of the given length has
+    /// been absorbed, without producing any plaintext.
+    ///
+    /// The ciphertext is decrypted one block at a time into a scratch block
+    /// which is fed to POLYVAL and then discarded.
+    fn verify_message(
+        &mut self,
+        associated_data_len: u64,
+        ciphertext: &[u8],
+        tag: &Tag,
+    ) -> Result<(), GcmSivError> {
+        check_ciphertext_lengths(ciphertext.len() as u64, associated_data_len)?;
+
+        let mut ctr = init_ctr(&self.enc_cipher, tag);
+        let mut block = polyval::Block::default();
+
+        for chunk in ciphertext.chunks(block.len()) {
+            ctr.write_keystream_block(&mut block);
+
+            for (a, b) in block.iter_mut().zip(chunk) {
+                *a ^= b;
+            }
+
+            // Zero-pad the final partial block, same as `update_padded`
+            block[chunk.len()..].fill(0);
+            self.polyval.update(&[block]);
+        }
+
+        #[cfg(feature = "zeroize")]
+        block.as_mut_slice().zeroize();
+
+        let expected_tag = self.finish_tag(associated_data_len, ciphertext.len() as u64);
+
+        use subtle::ConstantTimeEq;
+        if expected_tag.ct_eq(tag).into() {
+            Ok(())
+        } else {
+            Err(GcmSivError::AuthFailed)
+        }
+    }
+
+    /// Finish computing POLYVAL tag for AAD and buffer of the given length.
+    fn finish_tag(&mut self, associated_data_len: u64, buffer_len: u64) -> Tag {
+        let associated_data_bits = associated_data_len * 8;
+        let buffer_bits = buffer_len * 8;
+
+        let mut block = polyval::Block::default();
+        block[..8].copy_from_slice(&associated_data_bits.to_le_bytes());
+        block[8..].copy_from_slice(&buffer_bits.to_le_bytes());
+        self.polyval.update(&[block]);
+
+        let mut tag = self.polyval.finalize_reset();
+
+        // XOR the nonce into the resulting tag
+        for (i, byte) in tag[..12].iter_mut().enumerate() {
+            *byte ^= self.nonce[i];
+        }
+
+        // Clear the highest bit
+        tag[15] &= 0x7f;
+
+        self.enc_cipher.encrypt_block(&mut tag);
+        tag
+    }
+}
+
+/// Check plaintext and associated data lengths against the limits from
+/// RFC8452 § 6.
+///
+/// Every encryption method performs this check itself; it's exposed so
+/// oversized inputs can be rejected before allocating any buffers. Unlike
+/// the [`AeadInPlace`] methods, which can only return [`Error`], it reports
+/// which limit was exceeded.
+///
+/// ```
+/// use aes_gcm_siv::{check_lengths, GcmSivError, A_MAX, P_MAX};
+///
+/// assert_eq!(check_lengths(P_MAX, A_MAX), Ok(()));
+/// assert_eq!(check_lengths(P_MAX + 1, 0), Err(GcmSivError::PlaintextTooLong));
+/// assert_eq!(check_lengths(0, A_MAX + 1), Err(GcmSivError::AadTooLong));
+/// ```
+pub fn check_lengths(plaintext_len: u64, associated_data_len: u64) -> Result<(), GcmSivError> {
+    if plaintext_len > P_MAX {
+        return Err(GcmSivError::PlaintextTooLong);
+    }
+
+    if associated_data_len > A_MAX {
+        return Err(GcmSivError::AadTooLong);
+    }
+
+    Ok(())
+}
+
+/// Check detached ciphertext and associated data lengths.
+///
+/// Detached ciphertexts don't include the tag, so they're bounded by the
+/// maximum plaintext length rather than `C_MAX`.
+fn check_ciphertext_lengths(
+    ciphertext_len: u64,
+    associated_data_len: u64,
+) -> Result<(), GcmSivError> {
+    if ciphertext_len > P_MAX {
+        return Err(GcmSivError::CiphertextTooLong);
+    }
+
+    if associated_data_len > A_MAX {
+        return Err(GcmSivError::AadTooLong);
+    }
+
+    Ok(())
+}
+
+/// Derive the per-nonce message-authentication and message-encryption keys
+/// from the key-generating key.
+///
+/// # Constant time
+///
+/// Control flow and memory access here depend only on public values: the
+/// loop bounds and chunk lengths are fixed by the key size, and the counter
+/// and nonce are not secret. The only operation touching secret data is
+/// [`BlockEncrypt::encrypt_block`], so derivation is constant-time exactly
+/// when the underlying block cipher is (as is the case for the `aes` crate's
+/// AES-NI, ARMv8 and fixsliced software backends).
+fn derive_subkeys<Aes>(
+    key_generating_key: &Aes,
+    nonce: &Nonce,
+    mac_key: &mut polyval::Key,
+    enc_key: &mut Key<Aes>,
+) where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeySizeUser,
+{
+    let mut block = cipher::Block::<Aes>::default();
+    let mut counter = 0u32;
+
+    // Derive subkeys from the master key-generating-key in counter mode.
+    //
+    // From RFC8452 § 4: <https://tools.ietf.org/html/rfc8452#section-4>
+    //
+    // > The message-authentication key is 128 bit, and the message-encryption
+    // > key is either 128 (for AES-128) or 256 bit (for AES-256).
+    // >
+    // > These keys are generated by encrypting a series of plaintext blocks
+    // > that contain a 32-bit, little-endian counter followed by the nonce,
+    // > and then discarding the second half of the resulting ciphertext.  In
+    // > the AES-128 case, 128 + 128 = 256 bits of key material need to be
+    // > generated, and, since encrypting each block yields 64 bits after
+    // > discarding half, four blocks need to be encrypted.  The counter
+    // > values for these blocks are 0, 1, 2, and 3.  For AES-256, six blocks
+    // > are needed in total, with counter values 0 through 5 (inclusive).
+    for derived_key in &mut [mac_key.as_mut_slice(), enc_key.as_mut_slice()] {
+        for chunk in derived_key.chunks_mut(8) {
+            block[..4].copy_from_slice(&counter.to_le_bytes());
+            block[4..].copy_from_slice(nonce.as_slice());
+
+            key_generating_key.encrypt_block(&mut block);
+
+            // Key sizes which aren't a multiple of 64 bits take only a
+            // prefix of the final block's retained half
+            let len = chunk.len();
+            chunk.copy_from_slice(&block.as_slice()[..len]);
+
+            counter += 1;
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    block.as_mut_slice().zeroize();
+}
+
+/// Initialize counter mode.
+///
+/// From RFC8452 § 4: <https://tools.ietf.org/html/rfc8452#section-4>
+///
+/// > The initial counter block is the tag with the most significant bit
+/// > of the last byte set to one.
+///
+/// Only the first 32 bits of the counter block are incremented, as a little
+/// endian integer modulo 2^32, so the counter may wrap part way through a
+/// message. A message of [`P_MAX`] bytes is exactly 2^32 blocks, so no
+/// counter block is ever repeated within a message.
+#[inline]
 fn init_ctr<Aes>(cipher: Aes, nonce: &cipher::Block<Aes>) -> Ctr32LE<Aes>
 where
     Aes: BlockCipher<BlockSize = U16> + BlockEncrypt,
@@ -353,3 +1650,154 @@ where
     counter_block[15] |= 0x80;
     Ctr32LE::inner_iv_init(cipher, &counter_block)
 }
+
+/// Write `input` XORed with the keystream into `out`, which must be the same
+/// length, initializing each byte of `out` exactly once without reading it.
+fn apply_keystream_uninit<Aes>(mut ctr: Ctr32LE<Aes>, input: &[u8], out: &mut [MaybeUninit<u8>])
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt,
+{
+    debug_assert_eq!(input.len(), out.len());
+    let mut block = cipher::Block::<Aes>::default();
+
+    for (input_chunk, out_chunk) in input.chunks(block.len()).zip(out.chunks_mut(block.len())) {
+        ctr.write_keystream_block(&mut block);
+
+        for ((slot, a), b) in out_chunk.iter_mut().zip(input_chunk).zip(block.iter()) {
+            *slot = MaybeUninit::new(a ^ b);
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    block.as_mut_slice().zeroize();
+}
+
+/// Convert a slice of `MaybeUninit<u8>` into a `&mut [u8]`.
+///
+/// # Safety
+///
+/// Every element of `slice` must have been initialized.
+unsafe fn assume_init_mut(slice: &mut [MaybeUninit<u8>]) -> &mut [u8] {
+    // `MaybeUninit<u8>` is guaranteed to have the same layout as `u8`
+    &mut *(slice as *mut [MaybeUninit<u8>] as *mut [u8])
+}
+
+/// POLYVAL input which arrives split across arbitrary slice boundaries.
+///
+/// Input is buffered until a full block is available, so only the final
+/// partial block is zero-padded, exactly as if the slices had been
+/// concatenated and passed to `update_padded`.
+#[derive(Default)]
+struct PaddedInput {
+    /// Partial block.
+    block: polyval::Block,
+
+    /// Number of bytes in the partial block.
+    pos: usize,
+
+    /// Total number of bytes absorbed.
+    len: u64,
+}
+
+impl PaddedInput {
+    /// Absorb the given data into `polyval`.
+    fn update(&mut self, polyval: &mut Polyval, mut data: &[u8]) {
+        self.len = self.len.saturating_add(data.len() as u64);
+
+        if self.pos > 0 {
+            let n = core::cmp::min(self.block.len() - self.pos, data.len());
+            self.block[self.pos..][..n].copy_from_slice(&data[..n]);
+            self.pos += n;
+            data = &data[n..];
+
+            if self.pos < self.block.len() {
+                return;
+            }
+
+            polyval.update(&[self.block]);
+            self.pos = 0;
+        }
+
+        let (blocks, remaining) = data.split_at(data.len() - data.len() % self.block.len());
+        polyval.update_padded(blocks);
+        self.block[..remaining.len()].copy_from_slice(remaining);
+        self.pos = remaining.len();
+    }
+
+    /// Zero-pad and absorb any remaining partial block, returning the total
+    /// number of bytes absorbed.
+    fn finish(&mut self, polyval: &mut Polyval) -> u64 {
+        if self.pos > 0 {
+            self.block[self.pos..].fill(0);
+            polyval.update(&[self.block]);
+            self.pos = 0;
+        }
+
+        self.len
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for PaddedInput {
+    fn drop(&mut self) {
+        self.block.as_mut_slice().zeroize();
+    }
+}
+
+/// Counter mode keystream which can be applied across arbitrary slice
+/// boundaries.
+struct Keystream<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt,
+{
+    /// Counter mode core.
+    ctr: Ctr32LE<Aes>,
+
+    /// Current keystream block.
+    block: cipher::Block<Aes>,
+
+    /// Number of bytes of the current keystream block already used.
+    pos: usize,
+}
+
+impl<Aes> Keystream<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt,
+{
+    /// Create a new keystream from the given counter mode core.
+    fn new(ctr: Ctr32LE<Aes>) -> Self {
+        let block = cipher::Block::<Aes>::default();
+        let pos = block.len();
+        Self { ctr, block, pos }
+    }
+
+    /// XOR the next `data.len()` bytes of keystream into `data`.
+    fn apply(&mut self, mut data: &mut [u8]) {
+        while !data.is_empty() {
+            if self.pos == self.block.len() {
+                self.ctr.write_keystream_block(&mut self.block);
+                self.pos = 0;
+            }
+
+            let n = core::cmp::min(self.block.len() - self.pos, data.len());
+            let (chunk, rest) = core::mem::take(&mut data).split_at_mut(n);
+
+            for (a, b) in chunk.iter_mut().zip(&self.block[self.pos..]) {
+                *a ^= b;
+            }
+
+            self.pos += n;
+            data = rest;
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<Aes> Drop for Keystream<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt,
+{
+    fn drop(&mut self) {
+        self.block.as_mut_slice().zeroize();
+    }
+}
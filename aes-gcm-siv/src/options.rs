@@ -0,0 +1,100 @@
+//! Configurable output layout and authentication failure handling.
+
+/// Where the authentication tag is placed relative to the ciphertext.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TagPosition {
+    /// `ciphertext || tag`, as produced by [`Aead::encrypt`][aead::Aead::encrypt].
+    Suffix,
+
+    /// `tag || ciphertext`.
+    Prefix,
+
+    /// The tag is returned (or supplied) separately from the ciphertext.
+    Detached,
+}
+
+#[cfg(feature = "alloc")]
+impl Default for TagPosition {
+    fn default() -> Self {
+        Self::Suffix
+    }
+}
+
+/// What is left in the decryption buffer when a ciphertext fails to
+/// authenticate.
+///
+/// Decryption runs over the buffer before the tag can be checked, so on
+/// failure it holds unauthenticated plaintext until this policy is applied.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AuthFailurePolicy {
+    /// Re-encrypt the buffer, so it holds the ciphertext again.
+    Reencrypt,
+
+    /// Overwrite the buffer with zeroes.
+    Zero,
+
+    /// Leave the buffer as-is, skipping the extra pass over it.
+    ///
+    /// The buffer is left holding unauthenticated plaintext. Only use this if
+    /// the caller zeroizes or discards the buffer itself.
+    Skip,
+}
+
+impl Default for AuthFailurePolicy {
+    fn default() -> Self {
+        Self::Reencrypt
+    }
+}
+
+/// Options for [`AesGcmSiv::encrypt_with`][crate::AesGcmSiv::encrypt_with]
+/// and [`AesGcmSiv::decrypt_with`][crate::AesGcmSiv::decrypt_with].
+///
+/// The default options produce exactly the same output as
+/// [`Aead::encrypt`][aead::Aead::encrypt].
+#[cfg(feature = "alloc")]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct EncryptOptions {
+    pub(crate) tag_position: TagPosition,
+    pub(crate) append: bool,
+    pub(crate) auth_failure: AuthFailurePolicy,
+}
+
+#[cfg(feature = "alloc")]
+impl EncryptOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set where the authentication tag is placed.
+    pub fn tag_position(mut self, tag_position: TagPosition) -> Self {
+        self.tag_position = tag_position;
+        self
+    }
+
+    /// Shorthand for [`TagPosition::Detached`] (or [`TagPosition::Suffix`]
+    /// when `false`).
+    pub fn detached_tag(self, detached: bool) -> Self {
+        self.tag_position(if detached {
+            TagPosition::Detached
+        } else {
+            TagPosition::Suffix
+        })
+    }
+
+    /// Append the output to the existing contents of the output buffer,
+    /// rather than clearing it first.
+    pub fn append(mut self, append: bool) -> Self {
+        self.append = append;
+        self
+    }
+
+    /// Set what happens to the decrypted output on authentication failure.
+    pub fn auth_failure(mut self, policy: AuthFailurePolicy) -> Self {
+        self.auth_failure = policy;
+        self
+    }
+}
@@ -0,0 +1,104 @@
+//! Subkeys derived once for a fixed nonce.
+
+use crate::{AesGcmSiv, Cipher, GcmSivError, Nonce, Tag};
+use aead::KeyInit;
+use cipher::{consts::U16, BlockCipher, BlockEncrypt};
+
+/// The per-nonce message-authentication and message-encryption keys for a
+/// single `(key, nonce)` pair, created with [`AesGcmSiv::per_nonce_keys`].
+///
+/// Deriving these keys costs several AES block encryptions and an AES key
+/// schedule for every message. When the same message has to be processed
+/// more than once, e.g. computing its tag and later verifying it, holding
+/// on to a `PerNonceKeys` skips that work for every operation after the
+/// first. Each method produces exactly the same result as the
+/// corresponding method on [`AesGcmSiv`] with the same nonce.
+///
+/// # ⚠️ Warning
+///
+/// This does not relax the nonce requirements of AES-GCM-SIV. Encrypting
+/// two *different* messages with the same `PerNonceKeys` is nonce reuse,
+/// and reveals whether the two messages are identical. Only use it to
+/// repeat operations on one message, or to decrypt messages which were
+/// encrypted under its nonce.
+///
+/// ```
+/// # fn main() -> Result<(), aes_gcm_siv::GcmSivError> {
+/// use aes_gcm_siv::{aead::KeyInit, Aes256GcmSiv, Nonce};
+///
+/// let cipher = Aes256GcmSiv::new(&Default::default());
+/// let keys = cipher.per_nonce_keys(Nonce::from_slice(b"unique nonce"));
+///
+/// let tag = keys.compute_tag(b"header", b"message")?;
+///
+/// let mut buffer = *b"message";
+/// assert_eq!(keys.encrypt_in_place_detached(b"header", &mut buffer)?, tag);
+/// keys.decrypt_in_place_detached(b"header", &mut buffer, &tag)?;
+/// assert_eq!(&buffer, b"message");
+/// # Ok(())
+/// # }
+/// ```
+pub struct PerNonceKeys<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt,
+{
+    cipher: Cipher<Aes>,
+}
+
+impl<Aes> PerNonceKeys<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit + Clone,
+{
+    pub(crate) fn new(aead: &AesGcmSiv<Aes>, nonce: &Nonce) -> Self {
+        Self {
+            cipher: Cipher::new(&aead.key_generating_key, nonce),
+        }
+    }
+
+    /// Encrypt the given buffer in-place, returning the authentication tag.
+    pub fn encrypt_in_place_detached(
+        &self,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<Tag, GcmSivError> {
+        self.cipher
+            .clone()
+            .encrypt_in_place_detached(associated_data, buffer)
+    }
+
+    /// Decrypt the given buffer in-place, first authenticating it against
+    /// the given tag.
+    pub fn decrypt_in_place_detached(
+        &self,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &Tag,
+    ) -> Result<(), GcmSivError> {
+        self.cipher
+            .clone()
+            .decrypt_in_place_detached(associated_data, buffer, tag)
+    }
+
+    /// Compute the authentication tag for the given plaintext and associated
+    /// data, without encrypting the plaintext.
+    pub fn compute_tag(
+        &self,
+        associated_data: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Tag, GcmSivError> {
+        self.cipher.clone().compute_tag(associated_data, plaintext)
+    }
+
+    /// Authenticate the given ciphertext against a separately provided tag
+    /// without exposing the resulting plaintext.
+    pub fn verify_detached(
+        &self,
+        associated_data: &[u8],
+        ciphertext: &[u8],
+        tag: &Tag,
+    ) -> Result<(), GcmSivError> {
+        self.cipher
+            .clone()
+            .verify_detached(associated_data, ciphertext, tag)
+    }
+}
@@ -0,0 +1,120 @@
+//! Known-answer self-test.
+
+use crate::{Aes128GcmSiv, Aes256GcmSiv, AesGcmSiv, GcmSivError, Nonce, Tag};
+use aead::{AeadInPlace, KeyInit};
+use cipher::{consts::U16, BlockCipher, BlockEncrypt};
+
+/// Known-answer test vector.
+struct Vector<K: 'static> {
+    key: &'static K,
+    nonce: &'static [u8; 12],
+    aad: &'static [u8],
+    plaintext: &'static [u8],
+    ciphertext: &'static [u8],
+}
+
+/// Vectors from RFC8452 Appendix C.1: AEAD_AES_128_GCM_SIV
+/// <https://tools.ietf.org/html/rfc8452#appendix-C.1>
+#[rustfmt::skip]
+const AES128_VECTORS: &[Vector<[u8; 16]>] = &[
+    Vector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"",
+        plaintext: b"",
+        ciphertext: b"\xdc\x20\xe2\xd8\x3f\x25\x70\x5b\xb4\x9e\x43\x9e\xca\x56\xde\x25",
+    },
+    Vector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"",
+        plaintext: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\x74\x3f\x7c\x80\x77\xab\x25\xf8\x62\x4e\x2e\x94\x85\x79\xcf\x77\x30\x3a\xaf\x90\xf6\xfe\x21\x19\x9c\x60\x68\x57\x74\x37\xa0\xc4",
+    },
+    Vector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x02\x00",
+        plaintext: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\x00",
+        ciphertext: b"\x6b\xb0\xfe\xcf\x5d\xed\x9b\x77\xf9\x02\xc7\xd5\xda\x23\x6a\x43\x91\xdd\x02\x97\x24\xaf\xc9\x80\x5e\x97\x6f\x45\x1e\x6d\x87\xf6\xfe\x10\x65\x14",
+    },
+];
+
+/// Vectors from RFC8452 Appendix C.2: AEAD_AES_256_GCM_SIV
+/// <https://tools.ietf.org/html/rfc8452#appendix-C.2>
+#[rustfmt::skip]
+const AES256_VECTORS: &[Vector<[u8; 32]>] = &[
+    Vector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"",
+        plaintext: b"",
+        ciphertext: b"\x07\xf5\xf4\x16\x9b\xbf\x55\xa8\x40\x0c\xd4\x7e\xa6\xfd\x40\x0f",
+    },
+    Vector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"",
+        plaintext: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\x85\xa0\x1b\x63\x02\x5b\xa1\x9b\x7f\xd3\xdd\xfc\x03\x3b\x3e\x76\xc9\xea\xc6\xfa\x70\x09\x42\x70\x2e\x90\x86\x23\x83\xc6\xc3\x66",
+    },
+    Vector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x02\x00",
+        plaintext: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\x00",
+        ciphertext: b"\x43\xdd\x01\x63\xcd\xb4\x8f\x9f\xe3\x21\x2b\xf6\x1b\x20\x19\x76\x06\x7f\x34\x2b\xb8\x79\xad\x97\x6d\x82\x42\xac\xc1\x88\xab\x59\xca\xbf\xe3\x07",
+    },
+];
+
+/// Run a selection of the RFC 8452 Appendix C test vectors against both
+/// [`Aes128GcmSiv`] and [`Aes256GcmSiv`], including the empty plaintext and
+/// empty associated data cases.
+///
+/// This is intended as a power-on self-test on target hardware, where the
+/// crate's own test suite can't be run. It doesn't allocate.
+///
+/// Returns [`GcmSivError::SelfTestFailed`] if any ciphertext, tag, or
+/// decrypted plaintext doesn't match.
+pub fn run_rfc8452_vectors() -> Result<(), GcmSivError> {
+    for vector in AES128_VECTORS {
+        check(&Aes128GcmSiv::new(vector.key.into()), vector)?;
+    }
+
+    for vector in AES256_VECTORS {
+        check(&Aes256GcmSiv::new(vector.key.into()), vector)?;
+    }
+
+    Ok(())
+}
+
+/// Check a single vector.
+fn check<Aes, K>(cipher: &AesGcmSiv<Aes>, vector: &Vector<K>) -> Result<(), GcmSivError>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+    let nonce = Nonce::from_slice(vector.nonce);
+    let (expected_ciphertext, expected_tag) = vector.ciphertext.split_at(vector.plaintext.len());
+
+    let mut buffer = [0u8; 32];
+    let buffer = &mut buffer[..vector.plaintext.len()];
+    buffer.copy_from_slice(vector.plaintext);
+
+    let tag = cipher
+        .encrypt_in_place_detached(nonce, vector.aad, buffer)
+        .map_err(|_| GcmSivError::SelfTestFailed)?;
+
+    if buffer != expected_ciphertext || tag.as_slice() != expected_tag {
+        return Err(GcmSivError::SelfTestFailed);
+    }
+
+    cipher
+        .decrypt_in_place_detached(nonce, vector.aad, buffer, Tag::from_slice(expected_tag))
+        .map_err(|_| GcmSivError::SelfTestFailed)?;
+
+    if buffer != vector.plaintext {
+        return Err(GcmSivError::SelfTestFailed);
+    }
+
+    Ok(())
+}
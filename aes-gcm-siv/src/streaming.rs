@@ -0,0 +1,230 @@
+//! Two-pass streaming encryption.
+
+use crate::{
+    check_lengths, init_ctr, AesGcmSiv, Cipher, GcmSivError, Keystream, Nonce, PaddedInput, Tag,
+};
+use aead::KeyInit;
+use cipher::{consts::U16, BlockCipher, BlockEncrypt};
+
+#[cfg(feature = "alloc")]
+use {alloc::vec::Vec, polyval::universal_hash::UniversalHash};
+
+/// AES-GCM-SIV encryptor for messages too large to hold in memory at once.
+///
+/// # Two passes
+///
+/// AES-GCM-SIV is a synthetic IV construction: the authentication tag is
+/// computed over the entire plaintext and then used as the initial counter
+/// block for encryption. Encrypting a stream therefore requires reading the
+/// plaintext **twice**:
+///
+/// 1. Feed every plaintext chunk to [`StreamingEncryptor::update`], then call
+///    [`StreamingEncryptor::finish`] to obtain the tag.
+/// 2. Re-read the same plaintext from the beginning (e.g. by seeking back in
+///    a file) and pass each chunk to [`StreamingEncryptor::encrypt_chunk`],
+///    which replaces it with ciphertext in-place. Finally call
+///    [`StreamingEncryptor::finalize`].
+///
+/// Chunk boundaries may differ between the two passes, but the bytes must be
+/// identical. Only the total length is checked: if the data changes between
+/// passes the resulting ciphertext will fail to decrypt.
+pub struct StreamingEncryptor<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt,
+{
+    state: State<Aes>,
+}
+
+enum State<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt,
+{
+    /// First pass: absorbing plaintext into POLYVAL.
+    Authenticate {
+        cipher: Cipher<Aes>,
+        associated_data_len: u64,
+        input: PaddedInput,
+    },
+
+    /// Second pass: applying the keystream.
+    Encrypt {
+        keystream: Keystream<Aes>,
+        remaining: u64,
+    },
+
+    /// Transient state while `finish` moves between passes.
+    Poisoned,
+}
+
+impl<Aes> StreamingEncryptor<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+    /// Begin encrypting a message under the given nonce and associated data.
+    pub fn new(
+        aead: &AesGcmSiv<Aes>,
+        nonce: &Nonce,
+        associated_data: &[u8],
+    ) -> Result<Self, GcmSivError> {
+        let mut cipher = Cipher::new(&aead.key_generating_key, nonce);
+        let associated_data_len = cipher.update_aad_vectored(&[associated_data])?;
+
+        Ok(Self {
+            state: State::Authenticate {
+                cipher,
+                associated_data_len,
+                input: PaddedInput::default(),
+            },
+        })
+    }
+
+    /// First pass: absorb the next chunk of plaintext.
+    pub fn update(&mut self, chunk: &[u8]) -> Result<(), GcmSivError> {
+        match &mut self.state {
+            State::Authenticate {
+                cipher,
+                associated_data_len,
+                input,
+            } => {
+                check_lengths(input.len + chunk.len() as u64, *associated_data_len)?;
+                input.update(&mut cipher.polyval, chunk);
+                Ok(())
+            }
+            _ => Err(GcmSivError::StreamMismatch),
+        }
+    }
+
+    /// End the first pass, returning the authentication tag.
+    pub fn finish(&mut self) -> Result<Tag, GcmSivError> {
+        match core::mem::replace(&mut self.state, State::Poisoned) {
+            State::Authenticate {
+                mut cipher,
+                associated_data_len,
+                mut input,
+            } => {
+                let buffer_len = input.finish(&mut cipher.polyval);
+                let tag = cipher.finish_tag(associated_data_len, buffer_len);
+
+                self.state = State::Encrypt {
+                    keystream: Keystream::new(init_ctr(cipher.enc_cipher, &tag)),
+                    remaining: buffer_len,
+                };
+
+                Ok(tag)
+            }
+            state => {
+                self.state = state;
+                Err(GcmSivError::StreamMismatch)
+            }
+        }
+    }
+
+    /// Second pass: encrypt the next chunk of plaintext in-place.
+    ///
+    /// Returns an error, leaving `chunk` unmodified, if called before
+    /// [`StreamingEncryptor::finish`] or if the second pass would exceed the
+    /// length of the first.
+    pub fn encrypt_chunk(&mut self, chunk: &mut [u8]) -> Result<(), GcmSivError> {
+        match &mut self.state {
+            State::Encrypt {
+                keystream,
+                remaining,
+            } if chunk.len() as u64 <= *remaining => {
+                keystream.apply(chunk);
+                *remaining -= chunk.len() as u64;
+                Ok(())
+            }
+            _ => Err(GcmSivError::StreamMismatch),
+        }
+    }
+
+    /// End the second pass, checking that it covered exactly as many bytes
+    /// as the first.
+    pub fn finalize(self) -> Result<(), GcmSivError> {
+        match self.state {
+            State::Encrypt { remaining: 0, .. } => Ok(()),
+            _ => Err(GcmSivError::StreamMismatch),
+        }
+    }
+}
+
+/// Iterator which encrypts a message lazily, yielding the ciphertext in
+/// chunks followed by the authentication tag. Created with
+/// [`AesGcmSiv::encrypt_chunks`].
+///
+/// The tag is computed over the whole plaintext up front, since it is the
+/// initial counter block for encryption. Only the keystream is generated
+/// lazily: each call to `next` encrypts a single chunk.
+///
+/// The concatenation of every item is identical to the output of
+/// [`Aead::encrypt`][aead::Aead::encrypt].
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct EncryptChunks<'msg, Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt,
+{
+    chunks: core::slice::Chunks<'msg, u8>,
+    keystream: Keystream<Aes>,
+    tag: Option<Tag>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'msg, Aes> EncryptChunks<'msg, Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+    pub(crate) fn new(
+        aead: &AesGcmSiv<Aes>,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        plaintext: &'msg [u8],
+        chunk_size: usize,
+    ) -> Result<Self, GcmSivError> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+
+        let mut cipher = Cipher::new(&aead.key_generating_key, nonce);
+        let associated_data_len = cipher.update_aad_vectored(&[associated_data])?;
+        check_lengths(plaintext.len() as u64, associated_data_len)?;
+
+        cipher.polyval.update_padded(plaintext);
+        let tag = cipher.finish_tag(associated_data_len, plaintext.len() as u64);
+
+        Ok(Self {
+            chunks: plaintext.chunks(chunk_size),
+            keystream: Keystream::new(init_ctr(cipher.enc_cipher, &tag)),
+            tag: Some(tag),
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Aes> Iterator for EncryptChunks<'_, Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt,
+{
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Vec<u8>> {
+        match self.chunks.next() {
+            Some(chunk) => {
+                let mut buffer = chunk.to_vec();
+                self.keystream.apply(&mut buffer);
+                Some(buffer)
+            }
+            None => self.tag.take().map(|tag| tag.to_vec()),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.chunks.size_hint();
+        let tag = usize::from(self.tag.is_some());
+        (lower + tag, upper.map(|upper| upper + tag))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Aes> core::iter::FusedIterator for EncryptChunks<'_, Aes> where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt
+{
+}
@@ -0,0 +1,130 @@
+//! Nonce reuse detection for development and testing.
+
+use crate::{AesGcmSiv, GcmSivError, Nonce, Tag};
+use aead::{AeadCore, AeadInPlace, KeyInit};
+use cipher::{
+    consts::{U0, U12, U16},
+    BlockCipher, BlockEncrypt,
+};
+use std::{
+    collections::HashSet,
+    sync::{Mutex, MutexGuard},
+};
+
+/// What a [`TrackedCipher`] does when a nonce is reused.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub enum NonceReusePolicy {
+    /// Encrypt anyway, counting the reuse in
+    /// [`TrackedCipher::reuse_count`].
+    Count,
+
+    /// Refuse to encrypt, returning [`GcmSivError::NonceReused`].
+    Error,
+}
+
+/// Wrapper around [`AesGcmSiv`] which remembers every nonce it has
+/// encrypted under, to catch accidental nonce reuse in tests.
+///
+/// AES-GCM-SIV stays secure when a nonce is reused, but reveals whether
+/// two messages encrypted under the same nonce (and associated data) were
+/// equal. This type exists to find such reuse before it ships: it stores
+/// every nonce in a set that is never pruned, and serializes encryptions
+/// behind a lock, so it is not suitable for production use.
+///
+/// Decryption is not tracked, since decrypting under a nonce that was used
+/// for encryption is expected.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct TrackedCipher<Aes> {
+    aead: AesGcmSiv<Aes>,
+    policy: NonceReusePolicy,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    seen: HashSet<Nonce>,
+    reuse_count: u64,
+}
+
+impl<Aes> TrackedCipher<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+    /// Wrap the given cipher, handling reused nonces according to `policy`.
+    pub fn new(aead: AesGcmSiv<Aes>, policy: NonceReusePolicy) -> Self {
+        Self {
+            aead,
+            policy,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Number of encryptions which reused a nonce.
+    ///
+    /// Always zero with [`NonceReusePolicy::Error`], which refuses them.
+    pub fn reuse_count(&self) -> u64 {
+        self.state().reuse_count
+    }
+
+    /// Record `nonce` as used for encryption, applying the reuse policy.
+    pub fn check_nonce(&self, nonce: &Nonce) -> Result<(), GcmSivError> {
+        let mut state = self.state();
+
+        if state.seen.insert(*nonce) {
+            return Ok(());
+        }
+
+        match self.policy {
+            NonceReusePolicy::Count => {
+                state.reuse_count += 1;
+                Ok(())
+            }
+            NonceReusePolicy::Error => Err(GcmSivError::NonceReused),
+        }
+    }
+
+    /// Unwrap the underlying cipher.
+    pub fn into_inner(self) -> AesGcmSiv<Aes> {
+        self.aead
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        // The state is consistent between statements, so a panic while the
+        // lock was held leaves nothing to recover
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<Aes> AeadCore for TrackedCipher<Aes> {
+    type NonceSize = U12;
+    type TagSize = U16;
+    type CiphertextOverhead = U0;
+}
+
+impl<Aes> AeadInPlace for TrackedCipher<Aes>
+where
+    Aes: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+    fn encrypt_in_place_detached(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<Tag, aead::Error> {
+        self.check_nonce(nonce)?;
+        self.aead
+            .encrypt_in_place_detached(nonce, associated_data, buffer)
+    }
+
+    fn decrypt_in_place_detached(
+        &self,
+        nonce: &Nonce,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &Tag,
+    ) -> Result<(), aead::Error> {
+        self.aead
+            .decrypt_in_place_detached(nonce, associated_data, buffer, tag)
+    }
+}
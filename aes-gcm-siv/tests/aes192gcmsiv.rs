@@ -0,0 +1,110 @@
+//! AES-192-auth tag-SIV tests
+//!
+//! AES-GCM-SIV with a 192-bit key is not part of RFC 8452. These vectors
+//! were generated with OpenSSL's AES-192-GCM-SIV using the inputs of
+//! RFC8452 Appendix C.1.
+
+#![cfg(feature = "non-standard-sizes")]
+
+#[macro_use]
+mod common;
+
+use self::common::TestVector;
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, AeadInPlace, KeyInit, Payload};
+use aes_gcm_siv::Aes192GcmSiv;
+
+const TEST_VECTORS: &[TestVector<[u8; 24]>] = &[
+    TestVector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"",
+        plaintext: b"",
+        ciphertext: b"\xd4\x73\x7b\xf2\x6d\x29\xe1\xda\x38\x03\x92\xe4\xde\xca\x2d\x92"
+    },
+    TestVector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"",
+        plaintext: b"\x01\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\x0e\x49\xfb\x11\x96\x66\xc8\xae\x6b\x06\x06\x87\x5a\x84\x5e\xec\x14\x5f\x44\xae\x5b\x92\xe8\x34"
+    },
+    TestVector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"",
+        plaintext: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\x39\x38\xf3\xfe\x1d\xad\x84\x64\x11\x4d\xc4\x2a\x9f\x21\x31\xdf\x8b\x79\x4b\xc6\xd9\xaf\x9e\x5a\x8a\x96\x31\x8e"
+    },
+    TestVector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"",
+        plaintext: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\x75\xa9\x6f\x1f\x1c\xbf\xa9\x3e\x2c\xd6\x9e\x8a\x18\xbf\x3b\xab\x82\xe6\xa8\x1b\xe8\x03\xdc\x33\xf5\x6a\x63\x7f\xca\xa7\x0f\xec"
+    },
+    TestVector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"",
+        plaintext: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\x30\x22\xf4\x3d\x5c\xa4\x20\x34\x54\x20\xc5\x2d\xe0\x8d\xda\xa2\x8b\x8f\xb8\x40\xae\xb4\x1b\xd4\x4a\xdd\xc7\x8d\x07\xe0\x83\x5b\xde\xf0\x94\xdd\x94\xcb\x68\x94\x2b\x1b\x96\xa8\x5a\x8e\xab\x28"
+    },
+    TestVector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"",
+        plaintext: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\xb2\x84\x82\x64\x49\x5d\xde\xc5\x2a\x6f\x28\xa0\xb8\x11\x2e\x03\x1b\x78\xf4\xb7\x8e\xb6\x59\x0c\x54\xd6\x8f\x14\x23\x28\x50\xe2\xe4\xc4\xfd\xf7\x8b\x8c\x63\x77\x0e\xe0\xf0\x7d\x43\xde\xb5\x20\x6c\x64\x16\x3e\x99\x2c\xd4\x75\xd8\x47\xb9\x34\x8f\xf1\x79\x8a"
+    },
+    TestVector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"",
+        plaintext: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\xfb\x77\x08\x79\xe6\x94\x53\x3d\x40\xc7\x2a\x15\x63\x6c\x4d\x64\x06\xc8\xf8\x88\xb6\xee\x01\x16\xc0\x64\x62\x35\x2e\xb7\x71\x7d\x67\x84\xcf\x2a\xc3\x9f\x84\x34\x79\xde\xe4\x60\x81\x76\x6b\x91\xb9\x86\x33\xa0\x06\x49\x0c\xc7\x5e\x42\x1d\x20\x27\x33\x9e\x8d\xb6\xcc\xee\xa6\x06\xbf\x4d\x32\x83\xce\xb1\xcb\x44\x7e\x7c\xc0"
+    },
+    TestVector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"\x01",
+        plaintext: b"\x02\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\x8b\x2e\xed\x8f\x17\x2b\x22\x27\x89\x4e\x72\xc6\x73\x63\xad\x0e\xab\x00\x78\x4b\x92\xb1\x0c\xba"
+    },
+    TestVector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"\x01",
+        plaintext: b"\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\x30\x7a\x6c\xdf\xca\xa3\xca\x0d\x9f\x8a\x9c\x31\x41\xd2\x1d\x1b\x76\x4e\x3f\xfd\xd2\x53\xc2\xb0\xa4\x69\x5e\x2a"
+    },
+    TestVector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"\x01",
+        plaintext: b"\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\x30\xd9\x63\x04\x74\x42\x0e\xea\x90\xbe\xe4\xdb\xca\x3c\x4a\xe0\xaa\xfd\xe8\x48\x8b\xdc\xb2\x2d\xfa\x65\xfa\xd6\xe0\x94\xc6\xda"
+    },
+    TestVector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"\x01",
+        plaintext: b"\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\xab\xc2\xb1\x7c\xc5\xa7\xa8\x97\x45\xb6\x84\x84\x4b\x16\x99\x75\x75\x28\xf3\xa0\x08\x09\x0c\xdb\x0d\xd6\xbf\xbd\xfe\xa9\x55\x0e\x03\x90\x22\x34\xc1\xdb\x8a\xdc\xc4\xb6\xa2\xbc\x09\xc2\x84\x01"
+    },
+    TestVector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"\x01",
+        plaintext: b"\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\x33\x59\x10\xf4\x40\x2d\xb5\x1c\xec\xc5\xc3\x5f\xb4\x9e\xda\x85\x7f\x70\x5d\xe5\x5c\x9a\x69\x82\x45\x98\x42\x04\x31\xdd\x0a\xd3\xf1\xd0\x1d\xb4\x04\x11\x8f\x0b\x48\xb1\xe4\x05\xca\x43\x60\xf6\xbd\x5a\x9b\x90\xb7\x63\xe0\x5e\x69\xea\x0f\xfe\x3d\x85\x0a\xbf"
+    },
+    TestVector {
+        key: b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        nonce: b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        aad: b"\x01",
+        plaintext: b"\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x05\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        ciphertext: b"\x4a\xa3\x46\x76\xd0\x5f\x31\x98\x9d\xac\x48\x24\xdc\xba\xf9\x0d\x8b\x4d\xae\x36\x91\x85\x88\x97\x84\x27\xa4\x2d\x92\xf0\x32\x18\xaf\x04\x1c\x8a\xa4\x35\x78\xf5\x46\x34\xb5\xf9\xd2\x92\xc0\x47\xc7\xb1\x8b\xab\xa9\x7a\xc5\xa5\xd4\xd1\xf5\x2f\x46\x9c\x93\xa1\x06\x22\x06\xa7\xdf\x60\x69\xc4\xed\xfe\xfc\xda\x5b\x5c\x7b\x05"
+    },
+];
+
+tests!(Aes192GcmSiv, TEST_VECTORS);
@@ -0,0 +1,52 @@
+//! Tests for encrypting and decrypting into fixed-size arrays.
+//!
+//! Results are compared against [`Aead::encrypt`], which the `tests!`
+//! macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit};
+use aes_gcm_siv::{Aes256GcmSiv, GcmSivError, Nonce};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+#[test]
+fn encrypt_array() {
+    let (cipher, nonce) = setup();
+
+    let plaintext = [0x42; 32];
+    let expected = cipher.encrypt(nonce, &plaintext[..]).unwrap();
+
+    // Exactly large enough
+    let (out, len) = cipher.encrypt_array::<48>(nonce, b"", &plaintext).unwrap();
+    assert_eq!(len, 48);
+    assert_eq!(&out[..], expected.as_slice());
+
+    let (out, len) = cipher.decrypt_array::<32>(nonce, b"", &out).unwrap();
+    assert_eq!(len, 32);
+    assert_eq!(out, plaintext);
+
+    // Larger than needed
+    let (out, len) = cipher.encrypt_array::<64>(nonce, b"", &plaintext).unwrap();
+    assert_eq!(&out[..len], expected.as_slice());
+
+    let (out, len) = cipher.decrypt_array::<64>(nonce, b"", &expected).unwrap();
+    assert_eq!(&out[..len], &plaintext[..]);
+
+    // One byte too small
+    assert_eq!(
+        cipher.encrypt_array::<47>(nonce, b"", &plaintext),
+        Err(GcmSivError::BufferTooSmall)
+    );
+    assert_eq!(
+        cipher.decrypt_array::<31>(nonce, b"", &expected),
+        Err(GcmSivError::BufferTooSmall)
+    );
+}
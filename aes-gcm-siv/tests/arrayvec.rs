@@ -0,0 +1,36 @@
+//! `arrayvec::ArrayVec` buffer tests
+
+#![cfg(all(feature = "aes", feature = "arrayvec"))]
+
+use aes_gcm_siv::aead::{arrayvec::ArrayVec, generic_array::GenericArray, AeadInPlace, KeyInit};
+use aes_gcm_siv::Aes128GcmSiv;
+
+/// RFC8452 Appendix C.1, second vector
+const KEY: &[u8; 16] = b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00";
+const NONCE: &[u8; 12] = b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00";
+const PLAINTEXT: &[u8] = b"\x01\x00\x00\x00\x00\x00\x00\x00";
+const CIPHERTEXT: &[u8] = b"\xb5\xd8\x39\x33\x0a\xc7\xb7\x86\x57\x87\x82\xff\xf6\x01\x3b\x81\x5b\x28\x7c\x22\x49\x3a\x36\x4c";
+
+#[test]
+fn capacity_exact() {
+    let cipher = Aes128GcmSiv::new(GenericArray::from_slice(KEY));
+    let nonce = GenericArray::from_slice(NONCE);
+
+    let mut buffer: ArrayVec<u8, 24> = ArrayVec::new();
+    buffer.try_extend_from_slice(PLAINTEXT).unwrap();
+    cipher.encrypt_in_place(nonce, b"", &mut buffer).unwrap();
+    assert_eq!(CIPHERTEXT, buffer.as_slice());
+
+    cipher.decrypt_in_place(nonce, b"", &mut buffer).unwrap();
+    assert_eq!(PLAINTEXT, buffer.as_slice());
+}
+
+#[test]
+fn capacity_one_byte_short() {
+    let cipher = Aes128GcmSiv::new(GenericArray::from_slice(KEY));
+    let nonce = GenericArray::from_slice(NONCE);
+
+    let mut buffer: ArrayVec<u8, 23> = ArrayVec::new();
+    buffer.try_extend_from_slice(PLAINTEXT).unwrap();
+    assert!(cipher.encrypt_in_place(nonce, b"", &mut buffer).is_err());
+}
@@ -0,0 +1,23 @@
+//! AES backend detection tests
+
+#![cfg(feature = "aes")]
+
+use aes_gcm_siv::{backend_info, Backend};
+
+#[test]
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(aes_force_soft)))]
+fn matches_cpu_features() {
+    let expected = if std::is_x86_feature_detected!("aes") {
+        Backend::AesNi
+    } else {
+        Backend::Software
+    };
+
+    assert_eq!(backend_info(), expected);
+}
+
+#[test]
+#[cfg(aes_force_soft)]
+fn force_soft() {
+    assert_eq!(backend_info(), Backend::Software);
+}
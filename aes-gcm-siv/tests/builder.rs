@@ -0,0 +1,77 @@
+//! Tests for `AadBuilder` and incrementally supplied associated data.
+//!
+//! Results are compared against [`Aead::encrypt`], which the `tests!`
+//! macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, Nonce};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data spanning more than one block";
+
+/// Plaintexts covering the empty, partial-block and multi-block cases.
+const PLAINTEXTS: &[&[u8]] = &[b"", b"x", b"exactly sixteen!", &[0x42; 33]];
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+fn payload(msg: &[u8]) -> Payload<'_, 'static> {
+    Payload { msg, aad: AAD }
+}
+
+#[test]
+fn aad_builder() {
+    let (cipher, nonce) = setup();
+
+    for plaintext in PLAINTEXTS {
+        let expected = cipher.encrypt(nonce, payload(plaintext)).unwrap();
+
+        for chunk_size in &[1, 7, 16] {
+            let mut builder = cipher.begin(nonce);
+            for chunk in AAD.chunks(*chunk_size) {
+                builder.add_aad(chunk);
+            }
+            assert_eq!(expected, builder.encrypt(plaintext).unwrap());
+
+            let mut builder = cipher.begin(nonce);
+            for chunk in AAD.chunks(*chunk_size) {
+                builder.add_aad(chunk).add_aad(b"");
+            }
+            assert_eq!(*plaintext, builder.decrypt(&expected).unwrap());
+        }
+    }
+}
+
+#[test]
+fn aad_builder_chained() {
+    let (cipher, nonce) = setup();
+    let plaintext = PLAINTEXTS[3];
+    let expected = cipher.encrypt(nonce, payload(plaintext)).unwrap();
+
+    let ciphertext = cipher
+        .begin(nonce)
+        .aad(&AAD[..3])
+        .aad(&AAD[3..20])
+        .aad(&AAD[20..])
+        .encrypt(plaintext)
+        .unwrap();
+    assert_eq!(expected, ciphertext);
+
+    for chunk_size in &[1, 15, 16, 17] {
+        let builder = AAD
+            .chunks(*chunk_size)
+            .fold(cipher.begin(nonce), |builder, chunk| builder.aad(chunk));
+        assert_eq!(expected, builder.encrypt(plaintext).unwrap());
+
+        let builder = AAD
+            .chunks(*chunk_size)
+            .fold(cipher.begin(nonce), |builder, chunk| builder.aad(chunk));
+        assert_eq!(plaintext, builder.decrypt(&expected).unwrap());
+    }
+}
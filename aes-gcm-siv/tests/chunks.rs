@@ -0,0 +1,47 @@
+//! Tests for the `encrypt_chunks` iterator.
+//!
+//! Results are compared against [`Aead::encrypt`], which the `tests!`
+//! macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, Nonce};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+/// Plaintexts covering the empty, partial-block and multi-block cases.
+const PLAINTEXTS: &[&[u8]] = &[b"", b"x", b"exactly sixteen!", &[0x42; 33]];
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+#[test]
+fn encrypt_chunks() {
+    let (cipher, nonce) = setup();
+
+    for plaintext in PLAINTEXTS {
+        let payload = Payload {
+            msg: plaintext,
+            aad: AAD,
+        };
+        let expected = cipher.encrypt(nonce, payload).unwrap();
+
+        for chunk_size in &[1, 3, 7, 16, 17, 64] {
+            let chunks: Vec<Vec<u8>> = cipher
+                .encrypt_chunks(nonce, AAD, plaintext, *chunk_size)
+                .unwrap()
+                .collect();
+
+            let (tag, ciphertext) = chunks.split_last().unwrap();
+            assert_eq!(tag.len(), 16);
+            assert!(ciphertext.iter().all(|chunk| chunk.len() <= *chunk_size));
+            assert_eq!(expected, chunks.concat());
+        }
+    }
+}
@@ -30,6 +30,24 @@ macro_rules! tests {
             }
         }
 
+        #[test]
+        fn encrypt_in_place_detached() {
+            for vector in $vectors {
+                let key = GenericArray::from_slice(vector.key);
+                let nonce = GenericArray::from_slice(vector.nonce);
+                let mut buffer = vector.plaintext.to_vec();
+
+                let cipher = <$aead>::new(key);
+                let tag = cipher
+                    .encrypt_in_place_detached(nonce, vector.aad, &mut buffer)
+                    .unwrap();
+                let (expected_ciphertext, expected_tag) =
+                    vector.ciphertext.split_at(vector.plaintext.len());
+                assert_eq!(expected_ciphertext, &buffer[..]);
+                assert_eq!(expected_tag, &tag[..]);
+            }
+        }
+
         #[test]
         fn decrypt() {
             for vector in $vectors {
@@ -48,6 +66,23 @@ macro_rules! tests {
             }
         }
 
+        #[test]
+        fn decrypt_in_place_detached() {
+            for vector in $vectors {
+                let key = GenericArray::from_slice(vector.key);
+                let nonce = GenericArray::from_slice(vector.nonce);
+                let (ciphertext, tag) = vector.ciphertext.split_at(vector.plaintext.len());
+                let tag = GenericArray::clone_from_slice(tag);
+                let mut buffer = ciphertext.to_vec();
+
+                <$aead>::new(key)
+                    .decrypt_in_place_detached(nonce, vector.aad, &mut buffer, &tag)
+                    .unwrap();
+
+                assert_eq!(vector.plaintext, buffer.as_slice());
+            }
+        }
+
         #[test]
         fn decrypt_modified() {
             let vector = &$vectors[1];
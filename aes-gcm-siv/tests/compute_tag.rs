@@ -0,0 +1,38 @@
+//! Tests for computing a tag without encrypting the plaintext.
+//!
+//! Results are compared against [`Aead::encrypt`], which the `tests!`
+//! macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, Nonce};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+/// Plaintexts covering the empty, partial-block and multi-block cases.
+const PLAINTEXTS: &[&[u8]] = &[b"", b"x", b"exactly sixteen!", &[0x42; 33]];
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+fn payload(msg: &[u8]) -> Payload<'_, 'static> {
+    Payload { msg, aad: AAD }
+}
+
+#[test]
+fn compute_tag() {
+    let (cipher, nonce) = setup();
+
+    for plaintext in PLAINTEXTS {
+        let expected = cipher.encrypt(nonce, payload(plaintext)).unwrap();
+
+        let tag = cipher.compute_tag(nonce, AAD, plaintext).unwrap();
+        assert_eq!(&expected[plaintext.len()..], &tag[..]);
+    }
+}
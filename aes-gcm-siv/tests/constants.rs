@@ -0,0 +1,54 @@
+//! Tests for the exported size constants.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{
+    generic_array::{typenum::Unsigned, GenericArray},
+    Aead, AeadCore, KeyInit, KeySizeUser,
+};
+use aes_gcm_siv::{Aes128GcmSiv, Aes256GcmSiv, Key, Nonce};
+
+#[test]
+fn size_constants() {
+    assert_eq!(
+        aes_gcm_siv::NONCE_SIZE,
+        <Aes256GcmSiv as AeadCore>::NonceSize::USIZE
+    );
+    assert_eq!(
+        aes_gcm_siv::TAG_SIZE,
+        <Aes256GcmSiv as AeadCore>::TagSize::USIZE
+    );
+
+    let cipher = Aes256GcmSiv::new(&[0x01; 32].into());
+    let nonce = GenericArray::from_slice(b"unique nonce");
+    let ciphertext = cipher.encrypt(nonce, &b"plaintext"[..]).unwrap();
+
+    assert_eq!(Nonce::default().len(), aes_gcm_siv::NONCE_SIZE);
+    assert_eq!(ciphertext.len() - b"plaintext".len(), aes_gcm_siv::TAG_SIZE);
+}
+
+#[test]
+fn key_size() {
+    assert_eq!(
+        aes_gcm_siv::AES128_KEY_SIZE,
+        <Aes128GcmSiv as KeySizeUser>::KeySize::USIZE
+    );
+    assert_eq!(
+        aes_gcm_siv::AES256_KEY_SIZE,
+        <Aes256GcmSiv as KeySizeUser>::KeySize::USIZE
+    );
+    assert_eq!(
+        Key::<Aes128GcmSiv>::default().len(),
+        aes_gcm_siv::AES128_KEY_SIZE
+    );
+    assert_eq!(
+        Key::<Aes256GcmSiv>::default().len(),
+        aes_gcm_siv::AES256_KEY_SIZE
+    );
+
+    #[cfg(feature = "non-standard-sizes")]
+    assert_eq!(
+        aes_gcm_siv::AES192_KEY_SIZE,
+        <aes_gcm_siv::Aes192GcmSiv as KeySizeUser>::KeySize::USIZE
+    );
+}
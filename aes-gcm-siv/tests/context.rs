@@ -0,0 +1,49 @@
+//! Tests for `EncryptContext` and its reusable output buffer.
+//!
+//! Results are compared against the [`Aead`] methods, which the `tests!`
+//! macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, EncryptContext, GcmSivError, Nonce};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+/// Plaintexts covering the empty, partial-block and multi-block cases.
+const PLAINTEXTS: &[&[u8]] = &[b"", b"x", b"exactly sixteen!", &[0x42; 33]];
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+fn payload(msg: &[u8]) -> Payload<'_, 'static> {
+    Payload { msg, aad: AAD }
+}
+
+#[test]
+fn encrypt_context() {
+    let (cipher, nonce) = setup();
+    let mut context = EncryptContext::new();
+
+    for plaintext in PLAINTEXTS {
+        let expected = cipher.encrypt(nonce, payload(plaintext)).unwrap();
+
+        let ciphertext = context.encrypt(&cipher, nonce, payload(plaintext)).unwrap();
+        assert_eq!(expected, ciphertext);
+
+        let decrypted = context.decrypt(&cipher, nonce, payload(&expected)).unwrap();
+        assert_eq!(*plaintext, decrypted);
+
+        let mut modified = expected.clone();
+        modified[0] ^= 0xaa;
+        assert_eq!(
+            context.decrypt(&cipher, nonce, payload(&modified)),
+            Err(GcmSivError::AuthFailed)
+        );
+    }
+}
@@ -1,14 +1,22 @@
 //! Counter Wrap Tests
 //!
 //! The tests use `Aes256GcmSiv` and are crafted to test correct wrapping of
-//! the block counter.
+//! the block counter, along with a synthetic cipher which starts the counter
+//! near `0xFFFFFFFF` for a longer message.
 
 #[macro_use]
 mod common;
 
 use self::common::TestVector;
-use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
-use aes_gcm_siv::Aes256GcmSiv;
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, AeadInPlace, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, AesGcmSiv};
+use cipher::{
+    consts::{U1, U16},
+    inout::InOut,
+    Block, BlockBackend, BlockCipher, BlockClosure, BlockEncrypt, BlockSizeUser, Key, KeySizeUser,
+    ParBlocksSizeUser,
+};
+use core::mem::MaybeUninit;
 
 /// Test vectors from RFC8452 Appendix C.3. Counter Wrap Tests
 /// <https://tools.ietf.org/html/rfc8452#appendix-C.3>
@@ -30,3 +38,101 @@ const TEST_VECTORS: &[TestVector<[u8; 32]>] = &[
 ];
 
 tests!(Aes256GcmSiv, TEST_VECTORS);
+
+/// Tag produced by [`Synthetic`], whose low 32 bits (little endian) start the
+/// counter two blocks before it wraps.
+const SYNTHETIC_TAG: [u8; 16] =
+    *b"\xfe\xff\xff\xff\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a";
+
+/// Toy "block cipher" which maps every block with the high bit of its last
+/// byte clear (subkey derivation and tag encryption) to [`SYNTHETIC_TAG`] and
+/// leaves every block with it set (counter blocks) unchanged.
+///
+/// This pins the initial counter near `0xFFFFFFFF` regardless of the message,
+/// and makes the keystream equal to the counter blocks themselves.
+#[derive(Clone)]
+struct Synthetic;
+
+impl BlockSizeUser for Synthetic {
+    type BlockSize = U16;
+}
+
+impl KeySizeUser for Synthetic {
+    type KeySize = U16;
+}
+
+impl KeyInit for Synthetic {
+    fn new(_key: &Key<Self>) -> Self {
+        Self
+    }
+}
+
+impl BlockCipher for Synthetic {}
+
+impl BlockEncrypt for Synthetic {
+    fn encrypt_with_backend(&self, f: impl BlockClosure<BlockSize = U16>) {
+        f.call(&mut Synthetic)
+    }
+}
+
+impl ParBlocksSizeUser for Synthetic {
+    type ParBlocksSize = U1;
+}
+
+impl BlockBackend for Synthetic {
+    fn proc_block(&mut self, mut block: InOut<'_, '_, Block<Self>>) {
+        let input = *block.get_in();
+        *block.get_out() = if input[15] & 0x80 == 0 {
+            SYNTHETIC_TAG.into()
+        } else {
+            input
+        };
+    }
+}
+
+#[test]
+fn counter_wraps_mod_2_32() {
+    let cipher = AesGcmSiv::<Synthetic>::new(&Default::default());
+    let nonce = GenericArray::from_slice(&[0u8; 12]);
+    let plaintext: Vec<u8> = (0..83).collect();
+
+    // RFC 8452 § 4: the low 32 bits of the counter block are incremented
+    // modulo 2^32, i.e. 0xFFFFFFFE, 0xFFFFFFFF, 0, 1, 2, 3 here
+    let mut expected = plaintext.clone();
+    for (i, chunk) in expected.chunks_mut(16).enumerate() {
+        let mut counter_block = SYNTHETIC_TAG;
+        counter_block[15] |= 0x80;
+        let counter = 0xFFFF_FFFEu32.wrapping_add(i as u32);
+        counter_block[..4].copy_from_slice(&counter.to_le_bytes());
+
+        for (a, b) in chunk.iter_mut().zip(&counter_block) {
+            *a ^= b;
+        }
+    }
+    expected.extend_from_slice(&SYNTHETIC_TAG);
+
+    let ciphertext = cipher.encrypt(nonce, &*plaintext).unwrap();
+    assert_eq!(expected, ciphertext);
+    assert_eq!(plaintext, cipher.decrypt(nonce, &*ciphertext).unwrap());
+
+    let chunks: Vec<u8> = cipher
+        .encrypt_chunks(nonce, b"", &plaintext, 7)
+        .unwrap()
+        .flatten()
+        .collect();
+    assert_eq!(expected, chunks);
+
+    let mut out = [MaybeUninit::uninit(); 99];
+    let out = cipher
+        .encrypt_to_uninit(nonce, b"", &plaintext, &mut out)
+        .unwrap();
+    assert_eq!(expected, out);
+
+    let mut buffer = plaintext.clone();
+    let (head, tail) = buffer.split_at_mut(21);
+    let tag = cipher
+        .encrypt_in_place_split_detached(nonce, b"", head, tail)
+        .unwrap();
+    assert_eq!(SYNTHETIC_TAG, tag.as_slice());
+    assert_eq!(expected[..83], buffer);
+}
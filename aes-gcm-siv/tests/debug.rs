@@ -0,0 +1,15 @@
+//! Tests for the `Debug` impl, which must never print key material.
+
+#![cfg(feature = "aes")]
+
+use aes_gcm_siv::aead::KeyInit;
+use aes_gcm_siv::{Aes128GcmSiv, Aes256GcmSiv};
+
+#[test]
+fn debug_redacts_key() {
+    let cipher = Aes128GcmSiv::new(&[0x01; 16].into());
+    assert_eq!(format!("{:?}", cipher), "AesGcmSiv { key: <redacted> }");
+
+    let cipher = Aes256GcmSiv::new(&[0x01; 32].into());
+    assert_eq!(format!("{:?}", cipher), "AesGcmSiv { key: <redacted> }");
+}
@@ -0,0 +1,103 @@
+//! Tests for the detached-tag API, where the tag is kept apart from the
+//! ciphertext instead of being appended to it.
+//!
+//! Results are compared against [`Aead::encrypt`], which the `tests!`
+//! macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, AeadInPlace, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, Nonce};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+/// Plaintexts covering the empty, partial-block and multi-block cases.
+const PLAINTEXTS: &[&[u8]] = &[b"", b"x", b"exactly sixteen!", &[0x42; 33]];
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+fn payload(msg: &[u8]) -> Payload<'_, 'static> {
+    Payload { msg, aad: AAD }
+}
+
+#[test]
+fn encrypt_detached() {
+    let (cipher, nonce) = setup();
+
+    for plaintext in PLAINTEXTS {
+        let expected = cipher.encrypt(nonce, payload(plaintext)).unwrap();
+        let (expected_ciphertext, expected_tag) = expected.split_at(plaintext.len());
+
+        let (ciphertext, tag) = cipher.encrypt_detached(nonce, payload(plaintext)).unwrap();
+        assert_eq!(expected_ciphertext, ciphertext.as_slice());
+        assert_eq!(expected_tag, &tag[..]);
+    }
+}
+
+#[test]
+fn decrypt_detached() {
+    let (cipher, nonce) = setup();
+
+    for plaintext in PLAINTEXTS {
+        let expected = cipher.encrypt(nonce, payload(plaintext)).unwrap();
+        let (ciphertext, tag) = expected.split_at(plaintext.len());
+        let tag = GenericArray::from_slice(tag);
+
+        let decrypted = cipher
+            .decrypt_detached(nonce, payload(ciphertext), tag)
+            .unwrap();
+        assert_eq!(*plaintext, decrypted.as_slice());
+
+        let mut modified = ciphertext.to_vec();
+        modified.push(0);
+        assert!(cipher
+            .decrypt_detached(nonce, payload(&modified), tag)
+            .is_err());
+    }
+}
+
+#[test]
+fn decrypt_in_place_modified() {
+    let (cipher, nonce) = setup();
+
+    let mut ciphertext = b"plaintext message".to_vec();
+    let tag = cipher
+        .encrypt_in_place_detached(nonce, AAD, &mut ciphertext)
+        .unwrap();
+
+    // Tweak the first byte
+    ciphertext[0] ^= 0xaa;
+
+    // A failed decryption leaves the ciphertext unmodified
+    let mut buffer = ciphertext.clone();
+    assert!(cipher
+        .decrypt_in_place_detached(nonce, AAD, &mut buffer, &tag)
+        .is_err());
+    assert_eq!(ciphertext, buffer);
+}
+
+#[test]
+fn empty_plaintext_with_aad() {
+    let (cipher, nonce) = setup();
+
+    let tag = cipher
+        .encrypt_in_place_detached(nonce, AAD, &mut [])
+        .unwrap();
+
+    let payload = Payload { msg: b"", aad: AAD };
+    assert_eq!(&tag[..], cipher.encrypt(nonce, payload).unwrap().as_slice());
+    assert_eq!(tag, cipher.authenticate(nonce, AAD).unwrap());
+
+    assert!(cipher
+        .decrypt_in_place_detached(nonce, AAD, &mut [], &tag)
+        .is_ok());
+    assert!(cipher
+        .decrypt_in_place_detached(nonce, b"", &mut [], &tag)
+        .is_err());
+}
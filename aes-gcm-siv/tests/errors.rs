@@ -0,0 +1,104 @@
+//! Error reporting tests
+
+#![cfg(feature = "aes")]
+
+use aes_gcm_siv::{
+    aead::{AeadInPlace, KeyInit},
+    check_lengths, Aes128GcmSiv, GcmSivError, Nonce, Tag, A_MAX, P_MAX,
+};
+
+fn cipher() -> Aes128GcmSiv {
+    Aes128GcmSiv::new(&Default::default())
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn plaintext_too_long() {
+    // Lengths are summed before anything is copied, so a plaintext longer
+    // than `P_MAX` can be described by repeating one slice
+    let chunk = [0u8; 1 << 20];
+    let count = (P_MAX as usize >> 20) + 1;
+    let plaintext = vec![&chunk[..]; count];
+
+    assert_eq!(
+        cipher().encrypt_vectored_to_slice(&Nonce::default(), b"", &plaintext, &mut []),
+        Err(GcmSivError::PlaintextTooLong)
+    );
+}
+
+#[test]
+fn shape_errors_before_auth() {
+    let cipher = cipher();
+    let nonce = Nonce::default();
+
+    assert_eq!(
+        cipher.decrypt_to_slice(&nonce, b"", &[0u8; 15], &mut []),
+        Err(GcmSivError::CiphertextTooShort)
+    );
+    assert_eq!(
+        cipher.decrypt_to_slice(&nonce, b"", &[0u8; 17], &mut []),
+        Err(GcmSivError::BufferTooSmall)
+    );
+    assert_eq!(
+        cipher.decrypt_to_slice(&nonce, b"", &[0u8; 17], &mut [0u8; 1]),
+        Err(GcmSivError::AuthFailed)
+    );
+}
+
+#[test]
+fn into_aead_error() {
+    let mut buffer = [0u8; 4];
+    let result =
+        cipher().decrypt_in_place_detached(&Nonce::default(), b"", &mut buffer, &Tag::default());
+
+    assert_eq!(result, Err(aes_gcm_siv::Error));
+    assert_eq!(
+        aes_gcm_siv::Error::from(GcmSivError::AuthFailed),
+        aes_gcm_siv::Error
+    );
+}
+
+#[test]
+fn display() {
+    assert_eq!(
+        GcmSivError::AuthFailed.to_string(),
+        "ciphertext failed to authenticate"
+    );
+    assert_eq!(
+        GcmSivError::BufferTooSmall.to_string(),
+        "output buffer too small"
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn into_io_error() {
+    use std::{error::Error as _, io};
+
+    let err = io::Error::from(GcmSivError::AuthFailed);
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "ciphertext failed to authenticate");
+    assert!(err.source().is_none());
+    assert_eq!(
+        err.get_ref().and_then(|e| e.downcast_ref::<GcmSivError>()),
+        Some(&GcmSivError::AuthFailed)
+    );
+
+    let err = io::Error::from(GcmSivError::PlaintextTooLong);
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn check_lengths_matches_encrypt() {
+    assert_eq!(
+        check_lengths(P_MAX + 1, 0),
+        Err(GcmSivError::PlaintextTooLong)
+    );
+    assert_eq!(check_lengths(0, A_MAX + 1), Err(GcmSivError::AadTooLong));
+    // Plaintext is checked first when both limits are exceeded
+    assert_eq!(
+        check_lengths(P_MAX + 1, A_MAX + 1),
+        Err(GcmSivError::PlaintextTooLong)
+    );
+    assert_eq!(check_lengths(P_MAX, A_MAX), Ok(()));
+}
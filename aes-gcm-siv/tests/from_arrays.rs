@@ -0,0 +1,41 @@
+//! Tests for constructing ciphers from plain key arrays.
+//!
+//! Results are compared against [`KeyInit::new`], which the `tests!` macro
+//! checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes128GcmSiv, Aes256GcmSiv};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+fn payload(msg: &[u8]) -> Payload<'_, 'static> {
+    Payload { msg, aad: AAD }
+}
+
+macro_rules! check_from_array {
+    ($aead:ty, $key_size:expr) => {{
+        let key = [0x01; $key_size];
+
+        // Arrays convert into the `GenericArray` key and nonce types
+        let ciphertext = <$aead>::from(key)
+            .encrypt(NONCE.into(), payload(b"plaintext"))
+            .unwrap();
+
+        let expected = <$aead>::new(GenericArray::from_slice(&key))
+            .encrypt(GenericArray::from_slice(NONCE), payload(b"plaintext"))
+            .unwrap();
+        assert_eq!(expected, ciphertext);
+    }};
+}
+
+#[test]
+fn from_arrays() {
+    check_from_array!(Aes128GcmSiv, 16);
+    check_from_array!(Aes256GcmSiv, 32);
+
+    #[cfg(feature = "non-standard-sizes")]
+    check_from_array!(aes_gcm_siv::Aes192GcmSiv, 24);
+}
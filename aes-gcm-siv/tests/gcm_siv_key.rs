@@ -0,0 +1,57 @@
+//! Tests for selecting the key size at runtime with `GcmSivKey`.
+//!
+//! Results are compared against the concrete cipher types, which the
+//! `tests!` macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes128GcmSiv, Aes256GcmSiv, GcmSivError, GcmSivKey};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+fn payload(msg: &[u8]) -> Payload<'_, 'static> {
+    Payload { msg, aad: AAD }
+}
+
+macro_rules! check_variant {
+    ($aead:ty, $variant:path, $key_size:expr) => {{
+        let key = [0x01; $key_size];
+        let nonce = GenericArray::from_slice(NONCE);
+
+        let cipher = GcmSivKey::new(&key).unwrap();
+        assert!(matches!(cipher, $variant(_)));
+        assert_eq!(cipher.key_size(), $key_size);
+
+        // Same output as the concrete type, in both directions
+        let concrete = <$aead>::new(GenericArray::from_slice(&key));
+        let expected = concrete.encrypt(nonce, payload(b"plaintext")).unwrap();
+        let ciphertext = cipher.encrypt(nonce, payload(b"plaintext")).unwrap();
+        assert_eq!(expected, ciphertext);
+
+        let plaintext = GcmSivKey::from(concrete)
+            .decrypt(nonce, payload(&ciphertext))
+            .unwrap();
+        assert_eq!(plaintext, b"plaintext");
+    }};
+}
+
+#[test]
+fn gcm_siv_key() {
+    check_variant!(Aes128GcmSiv, GcmSivKey::Aes128, 16);
+    check_variant!(Aes256GcmSiv, GcmSivKey::Aes256, 32);
+
+    #[cfg(feature = "non-standard-sizes")]
+    check_variant!(aes_gcm_siv::Aes192GcmSiv, GcmSivKey::Aes192, 24);
+}
+
+#[test]
+fn gcm_siv_key_invalid_length() {
+    for len in [0, 15, 17, 31, 33] {
+        assert_eq!(
+            GcmSivKey::new(&[0u8; 33][..len]).unwrap_err(),
+            GcmSivError::InvalidKeyLength
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Tests for `AesGcmSiv` instantiated with block ciphers other than the
+//! built-in AES types.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes::Aes128;
+use aes_gcm_siv::{
+    aead::{generic_array::GenericArray, Aead, AeadInPlace, KeyInit},
+    Aes128GcmSiv, AesGcmSiv, GcmSiv,
+};
+use cipher::{
+    consts::{U16, U20},
+    BlockCipher, BlockClosure, BlockEncrypt, BlockSizeUser, Key, KeySizeUser,
+};
+
+/// Block cipher which delegates to AES-128 through the generic traits only,
+/// standing in for an externally provided (e.g. hardware-backed) cipher.
+#[derive(Clone)]
+struct Wrapped(Aes128);
+
+impl BlockSizeUser for Wrapped {
+    type BlockSize = U16;
+}
+
+impl KeySizeUser for Wrapped {
+    type KeySize = U16;
+}
+
+impl KeyInit for Wrapped {
+    fn new(key: &Key<Self>) -> Self {
+        Self(Aes128::new(key))
+    }
+}
+
+impl BlockCipher for Wrapped {}
+
+impl BlockEncrypt for Wrapped {
+    fn encrypt_with_backend(&self, f: impl BlockClosure<BlockSize = U16>) {
+        self.0.encrypt_with_backend(f)
+    }
+}
+
+/// Block cipher with a 160-bit key, which isn't a multiple of the 64 bits
+/// retained from each key derivation block. Only the first 128 bits of the
+/// key are used, so subkey derivation must agree with AES-128 on the shared
+/// prefix.
+#[derive(Clone)]
+struct OddKey(Aes128);
+
+impl BlockSizeUser for OddKey {
+    type BlockSize = U16;
+}
+
+impl KeySizeUser for OddKey {
+    type KeySize = U20;
+}
+
+impl KeyInit for OddKey {
+    fn new(key: &Key<Self>) -> Self {
+        Self(Aes128::new(GenericArray::from_slice(&key[..16])))
+    }
+}
+
+impl BlockCipher for OddKey {}
+
+impl BlockEncrypt for OddKey {
+    fn encrypt_with_backend(&self, f: impl BlockClosure<BlockSize = U16>) {
+        self.0.encrypt_with_backend(f)
+    }
+}
+
+const KEY: [u8; 16] = *b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00";
+const NONCE: [u8; 12] = *b"\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00";
+const AAD: &[u8] = b"\x01";
+const PLAINTEXT: &[u8] =
+    b"\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x03\x00\x00\x00";
+
+#[test]
+fn wrapped_matches_aes128() {
+    let nonce = GenericArray::from_slice(&NONCE);
+    let expected = Aes128GcmSiv::new(&KEY.into())
+        .encrypt(nonce, PLAINTEXT)
+        .unwrap();
+
+    let cipher = AesGcmSiv::<Wrapped>::new(&KEY.into());
+    let ciphertext = cipher.encrypt(nonce, PLAINTEXT).unwrap();
+    assert_eq!(expected, ciphertext);
+    assert_eq!(PLAINTEXT, cipher.decrypt(nonce, &*ciphertext).unwrap());
+}
+
+#[test]
+fn gcm_siv_alias() {
+    let nonce = GenericArray::from_slice(&NONCE);
+    let expected = AesGcmSiv::<Wrapped>::new(&KEY.into())
+        .encrypt(nonce, PLAINTEXT)
+        .unwrap();
+
+    let cipher: GcmSiv<Wrapped> = GcmSiv::new(&KEY.into());
+    assert_eq!(expected, cipher.encrypt(nonce, PLAINTEXT).unwrap());
+}
+
+#[test]
+fn odd_key_size_round_trip() {
+    let mut key = [0u8; 20];
+    key[..16].copy_from_slice(&KEY);
+
+    let nonce = GenericArray::from_slice(&NONCE);
+    let cipher = AesGcmSiv::<OddKey>::new(&key.into());
+
+    let mut buffer = PLAINTEXT.to_vec();
+    let tag = cipher
+        .encrypt_in_place_detached(nonce, AAD, &mut buffer)
+        .unwrap();
+
+    // The derived message-encryption key is 160 bits, but the first 128 bits
+    // (all that `OddKey` uses) are the same as for AES-128 with the same key
+    let mut expected = PLAINTEXT.to_vec();
+    let expected_tag = Aes128GcmSiv::new(&KEY.into())
+        .encrypt_in_place_detached(nonce, AAD, &mut expected)
+        .unwrap();
+    assert_eq!(expected, buffer);
+    assert_eq!(expected_tag, tag);
+
+    cipher
+        .decrypt_in_place_detached(nonce, AAD, &mut buffer, &tag)
+        .unwrap();
+    assert_eq!(PLAINTEXT, buffer.as_slice());
+}
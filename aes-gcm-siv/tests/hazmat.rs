@@ -0,0 +1,38 @@
+//! Subkey derivation tests
+
+#![cfg(all(feature = "aes", feature = "hazmat"))]
+
+use aes_gcm_siv::{
+    aead::{generic_array::GenericArray, KeyInit},
+    Aes128GcmSiv, Aes256GcmSiv,
+};
+use hex_literal::hex;
+
+/// Record authentication and encryption keys from RFC8452 Appendix C.1
+/// <https://tools.ietf.org/html/rfc8452#appendix-C.1>
+#[test]
+fn derive_subkeys_aes128() {
+    let key = hex!("01000000000000000000000000000000");
+    let nonce = hex!("030000000000000000000000");
+
+    let cipher = Aes128GcmSiv::new(&key.into());
+    let (mac_key, enc_key) = cipher.derive_subkeys(GenericArray::from_slice(&nonce));
+    assert_eq!(mac_key[..], hex!("d9b360279694941ac5dbc6987ada7377"));
+    assert_eq!(enc_key[..], hex!("4004a0dcd862f2a57360219d2d44ef6c"));
+}
+
+/// Record authentication and encryption keys from RFC8452 Appendix C.2
+/// <https://tools.ietf.org/html/rfc8452#appendix-C.2>
+#[test]
+fn derive_subkeys_aes256() {
+    let key = hex!("0100000000000000000000000000000000000000000000000000000000000000");
+    let nonce = hex!("030000000000000000000000");
+
+    let cipher = Aes256GcmSiv::new(&key.into());
+    let (mac_key, enc_key) = cipher.derive_subkeys(GenericArray::from_slice(&nonce));
+    assert_eq!(mac_key[..], hex!("b5d3c529dfafac43136d2d11be284d7f"));
+    assert_eq!(
+        enc_key[..],
+        hex!("b914f4742be9e1d7a2f84addbf96dec3456e3c6c05ecc157cdbf0700fedad222")
+    );
+}
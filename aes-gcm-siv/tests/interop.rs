@@ -0,0 +1,183 @@
+//! Interoperability tests against vectors generated by an independent
+//! AES-GCM-SIV implementation.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+mod common;
+
+use self::common::TestVector;
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes128GcmSiv, Aes256GcmSiv};
+
+/// AES-128-GCM-SIV vectors generated with OpenSSL 3.5 (via pyca/cryptography's
+/// `AESGCMSIV`) from pseudorandom inputs, covering associated data and
+/// plaintext lengths on and around block boundaries.
+const AES128_VECTORS: &[TestVector<[u8; 16]>] = &[
+    TestVector {
+        key: b"\xf4\x67\x94\x7b\xc9\xf4\xcd\x43\x7d\xe5\xa3\xda\x9c\xbb\x50\x1f",
+        nonce: b"\xa8\xe3\x00\x67\xc6\x3f\xf3\xea\x8b\x8d\x10\x00",
+        aad: b"",
+        plaintext: b"",
+        ciphertext: b"\x2b\x3f\xe2\xc9\x6b\x1b\xc1\x29\xc9\xe8\x0c\x84\xd5\x39\x01\x9b",
+    },
+    TestVector {
+        key: b"\xe2\xf9\x77\x57\xb2\xcb\x4f\x0f\xfd\xf0\x6a\x00\xf7\xd1\xcc\x81",
+        nonce: b"\x77\x8c\x97\xef\x1c\x67\x40\x69\xc5\x79\x4c\xde",
+        aad: b"",
+        plaintext: b"\x5c",
+        ciphertext: b"\xf5\x56\x80\xf7\xff\x33\x9e\x54\x7e\x13\xa7\x9c\xf2\xdf\x56\xfd\xf9",
+    },
+    TestVector {
+        key: b"\x88\xf3\x2e\x53\x52\xf4\xc8\xbf\x98\xdf\xe5\x88\xd1\x44\x2c\x2c",
+        nonce: b"\xce\xcd\xbd\x3a\x8c\xea\x72\x69\x49\x66\x9b\x7e",
+        aad: b"\xe1\xfe\xdf\xeb\xda\xef\xc1\x48\xb8\x95\x88\xd1\x2b",
+        plaintext: b"",
+        ciphertext: b"\x83\xc2\x2c\xaf\x2d\x04\xe0\x3d\x0a\x48\xe7\x4d\x46\x3d\x8b\x1c",
+    },
+    TestVector {
+        key: b"\xfa\x94\x57\x9f\x65\xc1\x37\x36\x57\x89\xba\x05\x40\xb0\x40\x8a",
+        nonce: b"\x4d\xdb\xb6\x35\xa9\x84\xe6\xde\xb4\xd5\x29\xb7",
+        aad: b"\x7a\x03\x2a\x48\x8e\xa8\x61\x7a\x57\x5f\x5a\x8d\x11\x6b\xe0\x5f",
+        plaintext: b"\xfa\x62\xc0\x0d\x4c\x91\xfc\xfa\x4f\xa9\x6c\x4a\xb1\x8c\xef\xd4",
+        ciphertext: b"\x72\x4a\x26\xde\xf8\xb3\x4d\xb7\x12\x62\x69\x21\x5b\x2e\xd7\x21\x0d\xc5\x6f\x44\x5d\x8e\x43\x3b\x54\x9f\xad\x7e\x32\x6c\x5f\xc8",
+    },
+    TestVector {
+        key: b"\x26\xed\xae\xbb\xb3\xad\xf5\x80\xea\x07\x3b\x27\xae\xc4\x70\xea",
+        nonce: b"\x7b\xe1\xd9\xd2\xab\x5e\xba\xe3\x0c\xed\xbd\xcf",
+        aad: b"\x1b\x99\x93\x98\x98\x9c\x7d\xa1\x20\x26\xfa\x39\xcf\xf9\xd6\xe7\x15",
+        plaintext: b"\xd3\x35\xfe\x85\xdb\x3e\x71\xa9\x84\x68\x88\xef\x61\x2b\xbf\x54\x84\xa1\xa5\xa5\x6e\xe1\xd6\x0c\x25\x5d\xb3\x13\x90\x6d\x21",
+        ciphertext: b"\xc5\xfc\xfe\x68\xaf\xa0\xdb\x6b\xcd\xfc\x52\x82\xb7\x60\x1b\x1f\xb9\x50\xcd\x50\x3f\x32\x90\x48\x42\x4a\x1a\x0b\xaa\x80\x98\xf6\xec\x31\xde\xa9\xe1\xd3\xdf\xcd\x23\xda\xcf\x50\x8a\x86\xb2",
+    },
+    TestVector {
+        key: b"\xbb\xa2\x7f\x52\xfa\x1e\x35\xb3\x95\x58\xed\xc6\x98\x2f\x63\x67",
+        nonce: b"\xda\xe5\xf3\x7d\x18\xb3\x49\x79\x29\x09\xa7\xd0",
+        aad: b"\x2f",
+        plaintext: b"\xb7\x50\x7d\xc0\x51\x5a\x1e\x4f\x6f\x68\x4f\x6c\xea\xdb\xca\x58\x00\x94\xeb\x13\x6d\x55\xd8\x55\xe8\x06\xc8\xcf\xe1\x96\x75\x38\x6a",
+        ciphertext: b"\x39\xe4\x6d\xf6\x74\xb1\x62\x04\xc3\x36\x0d\xc7\xdd\x67\x09\x63\x44\x5c\xb9\x3c\xed\xf9\x23\x14\xec\x2a\x69\x14\x9f\x6f\x04\xcb\xda\xab\xa9\xa4\x5f\xb5\x70\x0f\xd6\x06\x75\x97\x13\xd8\x00\xd5\x07",
+    },
+    TestVector {
+        key: b"\x26\x13\xf2\xd9\xb7\x1d\x06\xe9\xa6\x9d\x28\xd5\x14\x2d\x25\xaa",
+        nonce: b"\x79\x49\x16\x81\xf9\x14\x30\xae\xf5\xb4\xf0\x66",
+        aad: b"\xf7\xe3\x0b\xf6\x3d\xf5\x56\xc7\xf1\x3d\x18\x8b\x49\x8c\x6a\x8b\xcb\xed\x18\xd2\x41\x36\xfd\x0c\x25\x1e\x80\x07\x5b\x3d\x6f\x4c\x34\xa3\xbf\x13\xcd\x73\xed\xd4\xe1\x41\xad\x5a\x6d\xde\x7e\x5b\xdb\xcf\x57\xc5\x67\xf5\x05\x16\x38\x9d\x6d\x66\xa9\x63\xd6\xcb",
+        plaintext: b"\x10\x18\x88\x70\x05\x8e\xfe\x71\xcc\x92\xd5\x6e\x66\x2f\xca\xd6\x51\x2b\x42\x70\x1c\x4a\x60\x31\xa7\xa2\xbe\x9d\xc5\x4a\x98\xb8\xe7\x85\xbd\xba\xbe\x3f\x49\x9f\x07\xa1\x0e\x92\x4b\xa9\x48\x7e\x91\x73\xff\x01\x2e\x2b\xe3\x77\x55\xd7\xa1\x2f\x1e\x88\x65\xfe",
+        ciphertext: b"\xc6\xf9\xc3\x97\x44\x21\xff\xbb\x29\xe5\xea\x80\x82\xa4\x70\x79\x1b\x99\x0d\xcc\x20\x63\x18\x47\xcf\x95\x12\x8a\x36\x0d\xf0\xb1\x6f\x83\x48\xbe\x4e\x84\x0d\x6f\x6c\x94\xb7\xae\x3a\xa5\x5c\xdb\x3a\xbe\x62\x4c\x97\x96\x63\x08\xb7\x8b\x02\xdc\x70\xd0\xb8\x35\x5a\x47\x22\xba\x6a\xad\xc2\xc0\xff\x75\xc6\xbd\x61\xa8\x75\x4f",
+    },
+    TestVector {
+        key: b"\xa0\x3d\xc9\x45\xce\x71\x1d\x96\x11\xf4\xb6\x12\x00\xf9\x2e\xbc",
+        nonce: b"\x67\x64\xd8\x8e\x8c\x02\xb0\x87\xb5\x8d\x9f\x22",
+        aad: b"\xf3\xef\xc9\xb5\x62\x7a\x48\xe6\x60\x44\xa2\xbf\xeb\x46\x14\x15\x2f\xb5\x57\x1a",
+        plaintext: b"\xa0\xaf\x64\xc5\xc4\x57\x56\xa6\x02\x21\x6a\xfa\xa8\xc0\x15\x62\x74\x3a\x96\x99\xc6\xa4\xc4\xb6\x46\xd2\xa7\x57\x27\xc1\x04\x59\x96\x7a\xf4\x9a\xb1\x15\xa4\x10\x5f\x04\xde\x7a\x9a\x35\xc3\x79\xb5\x4c\x08\x5c\x81\x41\x70\xa6\x21\xef\xaf\x70\x1f\x9e\xbe\x5f\x35\x85\x60\x72\x21\x78\x3f\x71\xc4\x86\x9a\x49\x02\xfb\xa2\x06\x50\xe4\x39\x1e\x19\x21\xbc\x17\x88\x63\xb1\xf0\x6f\xd4\xe1\xb5\x07\xe0\x84\x97\x25\xbc\x4b\xbc\x9e\x18\xbf\xf7\x61\xe5\x74\xc2\x3a\xc8\xed\x8f\x88\xec\x07\x91\x55\xe4\x27\x25\x07\x1a\xa7\x00\xf4\x9b\x5f\x85\x99\x0f\xd2\x09\x73\x3f\xf3\x1a\xcc\x9b\x09\x6d\x04\x63\xb2\x66\xe0\x1c\x9a\x86\x40\xe7\x04\x70\xce\x8e\x2a\xa8\xd4\x4a\xa6\x84\x3a\xf4\x0a\x5c\xd9\x23\x0b\xa9\x67\xbd\x8f\x16\xd1\xd3\xb4\xc9\x3a\xae\x6d\x44\x82\x62\xfc\x0d\xe5\x77\x31\x3c\x3a\x9f\x01\x5e\x10\x43\x7b\xbd\x4c\xe4\x03\xa9\x8e\xf3\x03\x50\x18\x03\x5e\x61\x2d\x7d\x41\x9f\x7e\x03\x35\xdc\x59\xdb\x8e\xa5\x55\x48\x1b\xb4\x70\x28\x97\x82\xe5\xb4\x57\xa1\x0c\xb9\x47\xe9\x12\x5c\x73\xc1\x40\x58\xde\x1f\x21\x5f\xd9\x75\x4d\x1c\xf4",
+        ciphertext: b"\x52\xed\x9c\xc3\x93\xac\x8f\x7e\x46\x6d\x18\x70\x4e\x19\xc3\xf4\x8a\xe1\x09\x23\x65\x12\x74\xaa\xb8\x93\x05\xf7\xf2\xa5\x74\x7d\xf9\x65\xe0\xe3\x83\xab\x80\xf7\x16\xcb\xf8\x02\x15\x35\x5a\x12\xa9\xcd\x5e\x62\xe9\xd6\x53\x94\x6c\xf4\xb7\x27\x5d\x19\xcf\xdb\xd7\x7e\x87\x6e\x82\x42\xa4\xd7\xf5\xf5\xb2\xbd\x6e\xc4\x95\xf7\xa1\xaf\x52\x10\xc9\x35\xbe\x1f\x1d\x80\x7c\x99\xbf\xfd\xa1\xba\xfc\xa4\x8c\x03\x7b\xe1\x4c\x4b\x6a\x8d\xa8\x47\x9f\x61\x44\x17\x70\x43\x8a\x1d\x2b\x0d\xfb\xcc\x12\x48\x5f\xe2\x65\x17\xd9\x18\xe7\xcf\x34\xfe\x04\xcb\x25\x46\x93\x38\x90\xd7\xc2\xbf\xd1\xfc\x40\x9e\xeb\x62\xa9\x36\xda\x3d\x48\xe4\x10\xd4\x0d\x35\xa0\x14\x41\x87\x9c\xc2\x1f\x84\xfb\xee\x11\xa2\x92\x92\xee\xff\xea\xa0\xe6\x80\xca\xa4\x66\x84\x8a\xc6\x90\x2f\x36\xde\xa3\x7d\xc2\x08\x42\x24\xfb\x3c\x0d\xf4\x8d\xbd\xfe\xaa\x5e\x63\x43\xf4\xa5\x7c\x3c\xe4\x24\x3b\x8c\xce\x37\x17\xc4\x1f\x00\x45\x47\x8e\xbe\x98\xf1\x5d\x34\x69\xb3\xda\xb0\x23\x3c\x6d\xc5\x7c\xe4\x08\x12\x4e\xb5\x44\xb9\x80\xbd\xa0\x2d\xaf\xc6\x46\x7b\x58\x70\x35\x2f\x9c\x89\x3d\x92\xbe\x4a\xb6\x03\xa6\x3f\x1f\xd3\xdc\xed\xa4\x09",
+    },
+    TestVector {
+        key: b"\xdc\xa4\xc9\xe5\x2b\xed\x83\x2b\x59\x43\xa2\xbf\xf0\x0c\x48\x9f",
+        nonce: b"\x30\xc3\x9b\x9d\x0b\x2f\x32\x24\xff\xa6\x2d\x56",
+        aad: b"\x30\x6b\x61\x5e\x12\x5b\xcb\x36\x1b\x72\x08\x87\xbf\x66\x7a\x37\x65\xa6\xbd\x1d\x61\x8a\xf4\x68\xf8\xa3\x4e\x5d\xa3\x4e\x08\xef\xd0\xc9\xb6\xab\x91\x29\x4d\x2e\x93\xf2\x32\xd3\xff\xce\xd6\x41",
+        plaintext: b"\x79\x77\x37\x3f\x78\x02\x94\xf0\xc9\x52\x7f\x08\xf8\x1e\xd9\x43\xce\x8a\x11\x89\x04\x03\x0c\x30\x20\xb4\xb5\x8f\x15\xa6\xce\x9b\x33\x56\x07\x00\x7d\x3d\x4d\xa2\xa8\x68\x28\x58\x17\xe5\xbb\x16\xb9\x7d\x9c\x7f\xb4\x77\xd9\x79\x07\x76\xce\x26\xaa\x6d\x0d\x75\x9c\x40\xbb\x1b\x56\xee\xc8\x76\x18\x73\x3d\x16\x42\x04\x3d\x7b\xb3\x19\xb8\xf4\xc6\x8d\xc8\x0a\xf8\x19\x25\xc7\x84\x35\xf6\xb0\x7b\x39\xca\x6f\xd7\xcb\xe7\xb8\x8a\x16\x6a\xd1\x71\x2f\xae\xb5\xa5\x63\xfa\x2a\x9d\x69\x4a\xc3\xf3\x4c\x2f\x73\x1c\x98\x19\x05\xd4\x5a\xfa\x70\xec\xf1\x64\x4d\x69\x25\xd0\x4d\x4f\x61\xe0\x3d\xdb\xe7\xe6\x16\xc6\x58\x2d\xec\xd0\x72\xf9\xcf\xdb\xde\x4f\x3f\x2d\x92\xe8\xf9\x4f\xa8\xda\x17\xcf\xb7\x78\xc8\x6a\xc1\xc3\x87\xdb\x13\x90\x23\xc5\x07\x53\x0b\x99\x45\xc9\x5f\x45\xbd\x4c\x94\x56\x3e\x08\x08\xd0\xe1\x65\x70\x34\x1b\xc4\x3c\x90\xb9\x79\x08\xd0\x72\xb2\x9c\xee\xe9\x17\xfc\xc3\xf6\x81\xf9\x19\x27\xd4\xaf\xed\x63\x24\xe9\xb0\x8d\x07\xb3\x71\xaf\x7d\x2c\x6c\xdf\x83\x4b\x11\x5f\x9a\xa3\x34\x73\xe6\x3f\x5f\x9f\x07\x59\xe0\x25\x75\xdd\x33\x21\x7a\x03\xc5\x22\x65\x3e\x18\x6f\x62\x98\x2b\xe7\x28\xe9\xb0\xe4\x65\xa3\xd7\x50\xa5\x6b\xfd\x0a\xb2\x88\xae\xa1\x3d\x76\x3c\x90\x5e\x54\x60\x4a\xca\x2f\xcd\xdf\x2d\xa4",
+        ciphertext: b"\xea\xe8\xeb\x54\x72\x32\x8a\xa1\x4a\x93\x35\x5a\x86\xc9\x1b\xbb\x29\x44\x34\x6f\x00\x31\xbc\xfe\x02\x02\x50\x5a\x88\xd1\x6b\x2b\x9a\x2b\xb0\xbb\xc5\x6d\xf5\x33\x5c\xd8\x4a\xf5\xa0\x21\x10\x5f\xc0\xfe\xa4\xce\xb9\xcc\x1b\x41\x45\x66\x30\x57\x44\xfb\xd9\xb3\xbc\x36\xb9\xcf\xbb\xac\xc2\x4b\x62\x90\x9a\x77\x13\xac\x77\x7e\xa1\x1c\x6b\x82\xdf\x83\x5d\x01\x77\x00\xec\xe5\x0d\x05\xfc\x74\xe6\x53\xf0\xa1\xe6\xf3\xc8\x3d\xbe\x87\xb7\x66\xe6\xe8\x0e\x7d\xc7\x10\xd4\x34\x28\xea\xfd\xf8\x87\x06\x4a\xac\xb4\xa7\x3d\x6b\x44\x5c\x0a\x16\x63\x14\x3a\x21\x58\xed\x5f\xb0\x19\x01\xda\xec\x24\x78\xec\x57\xb4\x96\xdb\xd6\x8a\xe3\x81\xea\x4e\x40\x0f\xb1\x2c\x81\xb3\x60\x8d\x67\x7c\x80\x4b\x30\xda\x39\x4f\x4d\xc6\x38\xcf\xc5\x0c\x65\xc3\x9d\x18\x07\xe6\x2d\x70\xf7\x76\xc6\xc6\xae\xba\x6e\xf3\x0c\x4e\xbd\x67\xb8\x7f\x83\xa3\x7b\xb6\xb5\xb1\x39\x9f\x6a\xef\xa2\xff\x8e\xbd\xe8\x4a\xce\x04\x87\xc2\x9f\xf6\x59\x93\xb7\x2e\xb5\x10\xed\xf8\x01\x5a\x59\xe6\xcb\x43\x9a\xb5\xe8\xd8\x21\xdf\x42\xb0\x62\xcf\xb4\x3b\x6b\x5c\x89\x79\x04\xfd\xea\x98\x57\xa4\x96\x6a\xa5\x67\x95\x26\x96\xee\x30\xc7\x5b\x06\xb7\x31\xcf\x3f\xc3\xaa\x19\x11\x03\xd6\x31\xe6\x1c\xc6\x11\xc9\xd9\xf1\x54\x31\xd8\xbc\x95\xd3\x57\x50\x59\x0a\xc2\x06\x4a\x10\xe1\x67\xfb\x75\xad\x2d\xdb\x78\x56\xaa\x1d\xfe\x79",
+    },
+];
+
+/// AES-256-GCM-SIV vectors generated the same way as [`AES128_VECTORS`].
+const AES256_VECTORS: &[TestVector<[u8; 32]>] = &[
+    TestVector {
+        key: b"\xa3\x8b\xea\xd4\x25\xd4\x2a\x98\x13\x0a\x3f\xe8\xa8\xdd\x3d\x22\x22\xbf\xe2\x69\x4a\x91\x5a\x5f\xf5\xe4\x59\x3e\x96\x99\xef\x0d",
+        nonce: b"\xbd\x5d\xbc\xb3\xac\x0e\x68\xfb\x38\x4c\xb7\xaf",
+        aad: b"",
+        plaintext: b"",
+        ciphertext: b"\xa7\x30\xaf\xe1\xa2\xcd\x11\xca\x3c\x11\xc4\x98\x8e\x6a\x1a\x26",
+    },
+    TestVector {
+        key: b"\x5a\x0d\x5f\x0c\x32\x8e\x9e\xce\x54\x60\xd1\xcc\x47\xe2\xc1\x45\x47\xe9\xb4\x76\x00\xdc\xe8\x28\x88\x13\xfd\xbf\xc4\x7a\x5a\x2a",
+        nonce: b"\x18\x05\xa8\xf6\x6d\x28\xbb\xa5\xfe\x2c\x9e\x97",
+        aad: b"",
+        plaintext: b"\x5b",
+        ciphertext: b"\x84\xb7\xb8\xda\x82\x1b\x99\x15\x42\x17\x86\x3c\xc4\x6c\x6b\xed\xa8",
+    },
+    TestVector {
+        key: b"\xf0\x1e\xe5\x64\x97\x94\x73\x2d\x59\x67\xdd\x91\x93\x12\xcd\xb1\x79\x63\x04\x49\xbd\xae\xb2\x54\x73\x47\x5b\xf0\x14\x5d\x24\x01",
+        nonce: b"\x15\x49\xa4\xf4\x8e\xfa\x44\x3a\x38\xa1\x69\x67",
+        aad: b"\xd6\x17\x52\x1e\x8e\x9a\xf3\xd5\xc0\xf3\x7c\x27\xa8",
+        plaintext: b"",
+        ciphertext: b"\x38\x7d\xcc\xc6\xb5\x5b\xeb\xb3\x67\xd9\x56\x34\x51\xa2\x2c\xdd",
+    },
+    TestVector {
+        key: b"\xc5\x94\xd0\xb1\x27\xa8\x6d\xe1\xba\x5e\x17\xc7\xe1\xbd\xdc\x68\x5a\x87\xb0\x2e\x37\x78\x30\x99\x9a\x52\xe5\xdc\x48\xe2\x4d\x8b",
+        nonce: b"\x85\x95\x2c\x00\xff\xca\x54\xa9\xc9\x88\xf3\xe0",
+        aad: b"\x1c\x12\x09\x9e\xf6\x0f\xe9\x61\x3f\x8b\xdb\x56\x3b\x55\xbc\x31",
+        plaintext: b"\xd4\x11\x14\xc7\x2b\xc4\x18\x02\x1f\x86\x0a\x42\xa4\x81\x5b\x4e",
+        ciphertext: b"\x5d\x34\x70\xce\xe2\x8a\x53\x47\x81\xec\x54\xed\xad\x8f\xde\x70\x69\xc8\x1e\x1f\xbc\xa1\x4a\xe0\xd4\x29\x7b\x3e\x3e\xdf\x73\x4d",
+    },
+    TestVector {
+        key: b"\x20\x53\x11\x4c\xca\xb8\xc9\x1f\x2f\x46\xd6\x0c\x3e\xe3\x65\x0e\x9c\x45\x4b\xa9\x65\x91\x00\xd7\x8c\xcf\xdf\x5b\x65\x7f\xac\x8b",
+        nonce: b"\x3f\xf8\x7d\xf5\x86\x3e\xb3\x31\xc5\xfd\xfc\xba",
+        aad: b"\xfc\x86\x4b\xcb\x13\x67\x4d\x52\xbf\xed\x83\x74\xd4\x61\xe6\x76\x04",
+        plaintext: b"\x1c\x88\xf5\xb1\xb2\x5b\xeb\x7e\xa5\xef\xb4\x0d\x07\xfa\xfe\x64\x9d\x08\xbf\x77\x6d\x82\x32\x1a\xf7\x03\x09\x3a\x85\x16\x34",
+        ciphertext: b"\xed\x19\xb6\x84\xf0\xd2\xe4\x87\x6d\x49\x33\x3a\x47\x4a\x73\x65\x88\xae\xad\x9b\xff\x92\xca\xd3\x2f\xac\x94\x50\x7c\xef\x1f\x40\x63\x60\xaa\x97\xef\x41\x4e\x5f\xdb\x57\x1b\x77\x1d\x80\xe9",
+    },
+    TestVector {
+        key: b"\xdf\xaf\x4c\x6d\x14\x8a\x5e\x38\x25\x4f\xf7\xae\x8e\x11\xdc\xfa\x5a\x50\x34\x4f\xfb\xf5\x68\xe2\xaf\xc6\xc3\xdd\x19\x29\x75\x99",
+        nonce: b"\xdd\xbd\x92\xad\xf4\xe5\x9a\x68\x9f\x03\xab\x5e",
+        aad: b"\x6b",
+        plaintext: b"\x81\xb2\xbc\x75\xc7\x8a\xe9\xa5\x96\xca\x08\xf6\x86\x26\x1a\x1f\x45\x75\xc0\x8a\xdb\xbc\x2e\xc7\xaf\x6e\xe5\xda\x26\xf2\x47\x56\xf5",
+        ciphertext: b"\x21\xc1\x65\xcf\x3a\x5e\x27\x9f\x8d\x50\x0a\x30\xb0\xb5\x0c\x9a\x91\x92\xf4\xfe\x54\x81\xc2\x7e\xe4\xf1\xbb\x7b\x52\x95\xa4\x16\x3d\x10\xef\xb7\xca\xcc\x84\xd4\xa1\x87\xe9\x2f\xb0\xf2\xd9\xb2\xd7",
+    },
+    TestVector {
+        key: b"\xb9\x6e\xa1\x9a\x8b\xb2\x72\x5f\x59\x42\xd4\xf9\x0b\x92\x98\xf5\x37\x66\x5b\x1d\x6a\x1a\x49\x4b\x57\x93\x53\x7a\x6f\xc2\xea\x5d",
+        nonce: b"\xcc\x5f\x05\xd1\xdc\x93\x8c\xe0\x7f\x7f\x0c\x31",
+        aad: b"\x72\x6f\x24\xcd\xa1\x8e\xc6\x39\xff\x5e\x87\xd9\xff\x75\x96\xf8\x9a\x57\x3b\x46\x49\xdf\x25\xbc\xcc\xca\xdb\xe4\xb9\x66\x16\xcb\xf3\xc3\x8d\xef\x44\x27\x78\xca\x03\x5e\xf1\x58\x10\x07\x67\xc2\xf2\x70\x3d\xb1\xa0\x89\x2e\x4b\x86\x2a\xef\x9e\xca\xd6\xc7\xac",
+        plaintext: b"\x2c\xb8\x56\x63\x79\x3c\xef\xb0\x17\x5f\x6f\x69\x7f\x85\x48\xfc\xa1\xf2\xaa\x9c\x3e\x38\x23\x19\x4d\x94\xa4\x2e\xca\x85\x1e\x4c\x8a\xde\x84\xbb\xbd\x10\x2e\x93\x63\x6e\x6d\xdd\xa7\x98\x44\x66\x1a\x22\xb4\x6b\xb1\x21\x18\x1c\x76\xdc\x61\x06\xb4\x96\xcf\x8a",
+        ciphertext: b"\x45\xad\xb2\x24\x9d\xc8\xc8\xbd\x09\x5f\xd8\x00\x4f\x38\xa8\x66\x43\x48\x11\xa3\x4a\x05\x3c\x59\x8c\x57\x51\x94\xf4\x49\x78\x56\xcb\x20\x29\x0d\xcc\xb7\x85\x67\x5a\x0b\xa2\xed\x33\x3f\x20\x70\x92\xf8\x04\x54\x38\x2c\x21\x61\x9a\xf0\x7c\x84\x20\x3d\x91\x77\x26\x10\x87\x77\x2c\xd1\x1a\xc8\xb2\xa1\x2c\xdf\xb0\xc3\x2f\x69",
+    },
+    TestVector {
+        key: b"\xa5\x5b\x64\x27\xc3\xb5\x91\xe4\xac\x06\xdc\x24\x3b\x8e\xca\x2d\x45\x70\x2e\xc1\x5e\xf5\x4e\x0e\x3f\x5d\xb2\x77\xee\xfc\x1a\x87",
+        nonce: b"\x10\xe3\x6a\x20\x26\x7b\xbc\xfb\x08\xdb\x46\x07",
+        aad: b"\xd5\x71\xb1\x20\xb5\x54\xcc\xea\x9e\x4d\x3c\xc7\x21\xcc\x9c\xbe\x35\x3e\xb0\x47",
+        plaintext: b"\x9b\xc3\xd2\x42\xd4\xf5\x4e\x45\xf1\xea\x0e\x22\x33\x78\xca\x53\xdf\x26\x48\x34\x53\xf3\x42\x06\xa3\x61\x7c\xe0\xf9\xdd\xe8\xba\xd7\x6a\x30\x15\xa1\x78\x6b\xa7\xe9\xa6\xca\x55\x6f\x92\xa7\x0f\xe0\xe6\x5a\x34\xd4\x89\xbc\xb6\x35\x12\x79\xaa\x2c\x15\x67\xa0\x3f\xde\x7c\xa6\xc1\x2d\x70\xd8\x80\xfe\x8a\xf8\xaa\x24\x5b\xaa\xb5\x5a\x57\x5a\xcb\xff\x74\x86\x81\x78\xed\x03\xe6\xc2\xe6\xd5\x86\xa7\xa2\x3d\x40\x5e\x4c\x4f\xa2\xee\x44\x2a\x4e\xf4\xec\x63\xae\x92\x79\x5b\xfb\xa5\x80\xea\xd3\x3a\x12\xa9\xba\xbc\x3e\x8f\x3c\x32\xe7\xa9\x89\x17\xdd\x0d\x8d\xdd\xe0\xe2\x56\xdb\x49\xc8\xae\x66\xdd\xa1\xc2\xfa\x37\x34\x2f\x59\x77\xf0\xa9\xa4\x5d\xc8\x3a\xe5\x65\xb3\x09\x8b\x7f\xef\x51\x0f\x47\x66\xe7\x69\xa6\x92\x11\x1f\xa2\x35\x61\xc3\x07\xb4\x7b\x39\x4f\x6d\x42\x7f\x55\xf7\xf0\x8b\xf5\x41\x2c\xcc\x03\x18\x72\x55\xcd\x34\x06\x2d\x1c\x50\xbd\xa0\x62\xb5\x09\xcd\x87\x4b\xeb\xd5\xda\x29\x09\x8c\xc3\xaa\xc1\x40\x96\xdf\x6b\x5c\x3c\xd9\x0e\xf2\x13\xff\xb5\xe2\x01\x72\x46\x47\x0c\x89\xe6\xd4\x40\x54\xae\xf4\x13\x18\x87\x04\x9d",
+        ciphertext: b"\xcb\xd1\x20\x26\xd0\x02\xc0\xdb\xce\xfd\xe7\x55\x40\x4e\x5d\x0a\xe5\x22\xe5\x64\x86\x0b\x35\xcc\xc8\x2b\xeb\xb2\xff\x2d\xb5\x0a\x57\x3e\xc6\x29\x4b\x4f\xbe\x75\xba\x97\x7d\x57\xdd\x1a\x1f\x21\xe9\x76\xc7\xf9\xfc\x83\x17\xaa\x25\xe8\x5a\x7b\x80\x09\x93\xeb\xe7\x1d\x09\xe8\x85\x06\xcc\x69\x4c\x4a\xd5\x38\x6b\x38\xfe\xe1\xb3\xc8\x2e\x14\x48\x43\xab\x6a\x76\x60\x51\xdf\x38\xdd\x5b\x95\xa4\x58\xfe\x5a\x03\xf8\xb3\x0f\x77\xc1\x77\x3c\x58\x3e\xd0\x2e\x2c\x8a\xe5\x85\xd9\xe7\xf7\x77\x70\x70\xc4\xb3\xb0\xf4\xec\xb8\x20\x77\xcd\xed\x75\xc6\xd4\x1e\xab\x8c\x41\x16\xc3\x56\x52\xb9\x48\xcf\x77\x60\x90\x05\xca\xbe\x79\xa9\x91\xe2\xe1\x81\xc4\x0d\x80\x08\x55\xc1\xc4\x65\x92\x46\x43\x19\x26\xa7\xd0\xaf\x20\x35\x3e\xe3\x38\x46\x84\x73\x8e\xf4\xfb\x9e\xfa\xdf\xef\xc1\xa2\xaa\x07\xe8\x5e\x7f\x4f\xf7\x59\x79\xc0\x8f\xd3\xea\x7f\x4f\xab\xb2\x4f\xfe\xee\x0c\x9c\x0f\xa8\x54\x4e\x7d\x0c\x75\x0f\x6e\x57\xf2\xec\xca\x8f\x43\xad\x96\xc1\xe0\xfa\x87\xc2\xf6\xf7\x21\xa9\x28\x37\x8d\x53\x73\x58\x74\xa0\xbc\xb6\x19\xfe\xaa\x81\x6a\x29\x5f\xb8\x70\x1b\x9f\x82\x2b\x0a\xcc\x4f\xbc\x16\xcd\xe3\xad\x5f",
+    },
+    TestVector {
+        key: b"\xff\xfa\x18\x79\x33\x2f\x2e\x38\x67\x31\xdb\xca\xf4\x81\x88\x29\xd6\x61\xe4\x3f\x4e\x50\xfb\x0f\x6c\x53\x26\x73\xa5\x30\xd4\x54",
+        nonce: b"\xd5\x68\xfe\x57\xb8\xc1\x60\xc2\x54\x9e\x92\x4b",
+        aad: b"\xd8\x0f\xc8\x99\x4f\x6c\x10\x0e\xf8\xcc\xbb\x89\x7a\x32\x55\xa3\xf3\x17\x38\x1c\x36\x9f\xfb\x16\x1a\x1a\x40\xc3\xc9\xb7\x77\x93\x67\xa6\x85\xe4\x91\x01\xbc\xaf\xae\xbe\xab\x16\x7a\x6c\x4a\x7b",
+        plaintext: b"\x3e\x27\xf7\xcd\xed\xab\xb4\x87\x44\x55\x7a\x1b\xd9\xa8\xfc\x69\x7d\xa1\x97\x64\x50\x39\xd1\xec\x8f\x77\xdc\x24\x73\xa3\x93\xd5\xe9\x5b\x88\xba\xb8\x15\x09\xad\x73\xe4\x2c\x0d\x29\x33\xb9\x23\x93\x13\xf3\x43\xfe\xbc\xaa\x6a\x78\x9e\xbb\x25\x49\x2b\x9a\x9b\x9d\xd9\xa3\x64\x6f\xa0\xb7\xdf\xd0\x87\x8a\x94\x1b\xe3\x79\x87\x23\x55\xab\xd4\xf4\xe1\x09\xbe\x53\x7a\x8e\xae\x86\x22\x98\x8b\xce\xc2\x51\x91\x92\x9c\x29\x97\x12\x78\x90\xa7\x04\x01\x6d\x1c\xa9\xd4\x38\x07\x47\x94\x96\x7b\x19\xa8\x75\xb7\xa8\x90\xd9\x81\xca\xee\xa0\xcd\x42\x5f\xb2\xe6\x0c\x89\xb5\x8f\x7a\x27\x88\xe0\xe2\x19\xac\x1e\xe0\x93\x36\x0a\x9f\x9c\x6e\xf9\x31\x66\x19\xfe\xf0\xb9\xae\x11\x29\x2e\x4f\x3e\x9e\x03\x23\x3d\xae\xbc\x14\xbc\xe4\x27\x11\x2a\x99\x69\xc0\xef\x93\xfd\x09\xff\x10\x37\x01\x33\x31\xb8\x8d\x43\x59\xc7\x95\xaf\x68\x7f\xd4\xf0\x36\x3b\x5c\xb8\xef\x0e\xfb\x1a\xa8\xc7\x66\xd6\x89\x5c\xb1\x6a\xfd\x11\xa5\x06\x1c\xf4\x1b\x80\xe2\xc6\x01\x5a\xe5\xdd\x6a\x7c\x87\x09\x8f\x8c\xcb\x76\xf0\xc4\x5b\x19\xaf\xde\x9b\xd0\x43\x31\x4c\x53\x4d\x9c\x73\xcf\x92\x16\xe8\xea\x26\x07\x5e\x80\x5a\x6f\xc3\x9d\xeb\x08\xf7\x04\xd1\x2e\x04\xd2\xc7\xc9\x93\xa6\xee\x01\xa3\x45\x7b\xcb\x05\xbe\x80\xeb\x56\x77\x73\xdf\xcf\xef\xe6\xc0",
+        ciphertext: b"\xf3\x60\x6b\xd3\x03\x48\x11\xaf\x7d\x4b\xf0\x86\xab\x28\x11\x6d\x9a\x08\xa1\x78\xfe\xa7\xc3\xf1\x15\x12\xfe\x3a\xdf\xc4\x40\x53\x10\xa8\x49\x3c\x72\x82\x92\xb1\xcf\xdc\x76\xfd\xf7\xfe\x29\x28\xe6\xb2\x81\x80\x8c\x1a\xd6\x0f\x9e\x4f\xf9\xd4\x80\x7d\xe6\xd3\xc6\x1f\x95\x23\x76\xf9\xbf\xb9\x39\x08\x01\x7e\xe3\xd2\x96\x98\xa8\x7b\x74\x76\x29\x39\x80\xf2\x45\xa1\x24\x1d\x9f\x4d\xc6\x27\x7f\xc2\xaf\x80\x36\x63\xca\xbb\x95\xed\x8d\x6c\x51\x7a\x52\x71\x4c\xba\x17\x41\x37\xa1\xe7\x2c\x78\x1d\xe6\x97\x18\x85\xab\x46\xf4\x53\x86\xc6\x58\x4b\xf3\xec\xce\x5f\xea\x0b\x91\xe3\x2e\x2e\xcc\x71\x68\x8a\xa4\xec\x3b\xd0\xde\x85\x2a\xf2\x74\x89\x98\x8f\x11\x53\xde\x3a\xec\x7d\x97\x98\x5a\x1b\x5e\x12\xbb\x18\x9f\xf9\xcb\x6b\x79\xa2\x0a\x2f\x2d\x7c\x32\x41\x38\xd4\xb9\x0e\x59\x3a\xcb\x4c\x67\x45\x16\xe1\x67\x26\x52\xe7\x6a\xac\x48\x16\x09\xe4\xcc\xe1\x6f\x9a\x49\x49\x31\xdd\xc6\xfa\x9d\xe3\x13\x35\xf0\x14\x77\x36\x7b\xc5\xf8\xcd\x40\x2b\xa9\x67\xf9\x05\xeb\xb4\x7c\xfa\x9a\xd1\xdb\xa1\x2a\xc7\x2e\x51\x82\xa0\x93\x89\x21\xf1\x7a\x85\xeb\x21\x68\x8b\x37\x52\x4c\x65\x09\xd3\xec\x79\xf7\x45\xe4\xa2\xac\xe4\x2a\x76\xa1\xbd\x57\x2c\x8b\x09\x4a\xa8\x0b\xcd\x3e\x26\xdb\x3a\xef\xc0\x13\x73\x53\x78\xf5\x28\x35\xd1\x24\x5a\xaf\x10\x59\x53\x1a\x98\x74\x60\xcb\x2b\x17\x80\x80\x43",
+    },
+];
+
+macro_rules! interop_test {
+    ($name:ident, $aead:ty, $vectors:expr) => {
+        #[test]
+        fn $name() {
+            for vector in $vectors {
+                let cipher = <$aead>::new(GenericArray::from_slice(vector.key));
+                let nonce = GenericArray::from_slice(vector.nonce);
+
+                let ciphertext = cipher
+                    .encrypt(
+                        nonce,
+                        Payload {
+                            msg: vector.plaintext,
+                            aad: vector.aad,
+                        },
+                    )
+                    .unwrap();
+                assert_eq!(ciphertext.as_slice(), vector.ciphertext);
+
+                let plaintext = cipher
+                    .decrypt(
+                        nonce,
+                        Payload {
+                            msg: vector.ciphertext,
+                            aad: vector.aad,
+                        },
+                    )
+                    .unwrap();
+                assert_eq!(plaintext.as_slice(), vector.plaintext);
+            }
+        }
+    };
+}
+
+interop_test!(aes128gcmsiv, Aes128GcmSiv, AES128_VECTORS);
+interop_test!(aes256gcmsiv, Aes256GcmSiv, AES256_VECTORS);
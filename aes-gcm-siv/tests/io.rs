@@ -0,0 +1,215 @@
+//! `std::io` adapter tests
+
+#![cfg(all(feature = "aes", feature = "std"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit};
+use aes_gcm_siv::{Aes256GcmSiv, DecryptReader, EncryptWriter};
+use std::{
+    env, fs,
+    io::{self, Read, Write},
+    path::PathBuf,
+    process,
+};
+
+const KEY: &[u8; 32] = &[0x42; 32];
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+fn plaintext() -> Vec<u8> {
+    (0..1000).map(|i| (i % 251) as u8).collect()
+}
+
+fn encrypt(plaintext: &[u8]) -> Vec<u8> {
+    let cipher = Aes256GcmSiv::new(GenericArray::from_slice(KEY));
+    let nonce = GenericArray::from_slice(NONCE);
+    let mut writer = EncryptWriter::new(cipher, nonce, AAD, Vec::new());
+
+    for chunk in plaintext.chunks(33) {
+        writer.write_all(chunk).unwrap();
+    }
+
+    writer.finish().unwrap()
+}
+
+fn decrypt(ciphertext: &[u8]) -> io::Result<Vec<u8>> {
+    let cipher = Aes256GcmSiv::new(GenericArray::from_slice(KEY));
+    let nonce = GenericArray::from_slice(NONCE);
+    let mut reader = DecryptReader::new(cipher, nonce, AAD, ciphertext);
+
+    let mut plaintext = Vec::new();
+    reader.read_to_end(&mut plaintext)?;
+    Ok(plaintext)
+}
+
+#[test]
+fn encrypt_writer() {
+    let plaintext = plaintext();
+    let cipher = Aes256GcmSiv::new(GenericArray::from_slice(KEY));
+    let nonce = GenericArray::from_slice(NONCE);
+    let expected = cipher
+        .encrypt(
+            nonce,
+            aes_gcm_siv::aead::Payload {
+                msg: &plaintext,
+                aad: AAD,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(expected, encrypt(&plaintext));
+}
+
+#[test]
+fn decrypt_reader() {
+    let plaintext = plaintext();
+    assert_eq!(plaintext, decrypt(&encrypt(&plaintext)).unwrap());
+    assert_eq!(Vec::<u8>::new(), decrypt(&encrypt(b"")).unwrap());
+}
+
+#[test]
+fn decrypt_reader_modified() {
+    let mut ciphertext = encrypt(&plaintext());
+    ciphertext[0] ^= 0xaa;
+
+    let err = decrypt(&ciphertext).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn decrypt_reader_too_short() {
+    let err = decrypt(&[0; 15]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+/// Reader which fails with [`io::ErrorKind::WouldBlock`] once, after the
+/// first `fail_at` bytes.
+struct BlockOnce<'a> {
+    data: &'a [u8],
+    fail_at: usize,
+}
+
+impl Read for BlockOnce<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.fail_at == 0 {
+            self.fail_at = usize::MAX;
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        let n = buf.len().min(self.data.len()).min(self.fail_at);
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        self.fail_at = self.fail_at.saturating_sub(n);
+        Ok(n)
+    }
+}
+
+#[test]
+fn decrypt_reader_resumes() {
+    let plaintext = plaintext();
+    let ciphertext = encrypt(&plaintext);
+    let cipher = Aes256GcmSiv::new(GenericArray::from_slice(KEY));
+    let nonce = GenericArray::from_slice(NONCE);
+    let inner = BlockOnce {
+        data: &ciphertext,
+        fail_at: 500,
+    };
+    let mut reader = DecryptReader::new(cipher, nonce, AAD, inner);
+
+    let mut buf = [0u8; 64];
+    let err = reader.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+    let mut output = Vec::new();
+    reader.read_to_end(&mut output).unwrap();
+    assert_eq!(output, plaintext);
+}
+
+#[test]
+fn decrypt_reader_poisoned() {
+    let mut ciphertext = encrypt(&plaintext());
+    ciphertext[0] ^= 0xaa;
+
+    let cipher = Aes256GcmSiv::new(GenericArray::from_slice(KEY));
+    let nonce = GenericArray::from_slice(NONCE);
+    let mut reader = DecryptReader::new(cipher, nonce, AAD, ciphertext.as_slice());
+
+    let mut buf = [0u8; 64];
+    let err = reader.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    // The underlying reader is exhausted, but the error doesn't change
+    let again = reader.read(&mut buf).unwrap_err();
+    assert_eq!(again.kind(), err.kind());
+    assert_eq!(again.to_string(), err.to_string());
+}
+
+/// Path in the temporary directory unique to this process and `name`.
+fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("aes-gcm-siv-{}-{}", process::id(), name))
+}
+
+#[test]
+fn file_roundtrip() {
+    let cipher = Aes256GcmSiv::new(GenericArray::from_slice(KEY));
+    let nonce = GenericArray::from_slice(NONCE);
+    let (src, enc, dec) = (
+        temp_path("roundtrip.txt"),
+        temp_path("roundtrip.enc"),
+        temp_path("roundtrip.dec"),
+    );
+
+    let plaintext = plaintext();
+    fs::write(&src, &plaintext).unwrap();
+
+    cipher.encrypt_file(nonce, AAD, &src, &enc, 1000).unwrap();
+    assert_eq!(fs::read(&enc).unwrap(), encrypt(&plaintext));
+
+    cipher.decrypt_file(nonce, AAD, &enc, &dec, 1000).unwrap();
+    assert_eq!(fs::read(&dec).unwrap(), plaintext);
+
+    for path in [src, enc, dec] {
+        fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn file_max_len() {
+    let cipher = Aes256GcmSiv::new(GenericArray::from_slice(KEY));
+    let nonce = GenericArray::from_slice(NONCE);
+    let (src, dst) = (temp_path("max_len.txt"), temp_path("max_len.enc"));
+
+    fs::write(&src, plaintext()).unwrap();
+
+    let err = cipher
+        .encrypt_file(nonce, AAD, &src, &dst, 999)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert!(!dst.exists());
+
+    let err = cipher
+        .decrypt_file(nonce, AAD, &src, &dst, 983)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(!dst.exists());
+
+    fs::remove_file(src).unwrap();
+}
+
+#[test]
+fn decrypt_file_modified() {
+    let cipher = Aes256GcmSiv::new(GenericArray::from_slice(KEY));
+    let nonce = GenericArray::from_slice(NONCE);
+    let (src, dst) = (temp_path("modified.enc"), temp_path("modified.dec"));
+
+    let mut ciphertext = encrypt(&plaintext());
+    ciphertext[0] ^= 0xaa;
+    fs::write(&src, ciphertext).unwrap();
+
+    let err = cipher
+        .decrypt_file(nonce, AAD, &src, &dst, 1000)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(!dst.exists());
+
+    fs::remove_file(src).unwrap();
+}
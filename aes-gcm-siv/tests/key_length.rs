@@ -0,0 +1,22 @@
+//! Tests for mapping key length errors to `GcmSivError`.
+
+#![cfg(feature = "aes")]
+
+use aes_gcm_siv::aead::KeyInit;
+use aes_gcm_siv::{Aes128GcmSiv, Aes256GcmSiv, GcmSivError};
+
+#[test]
+fn new_from_slice() {
+    for len in [15, 17, 31, 32, 33] {
+        let result = Aes128GcmSiv::new_from_slice(&[0u8; 33][..len]).map_err(GcmSivError::from);
+        assert_eq!(result.err(), Some(GcmSivError::InvalidKeyLength));
+    }
+
+    for len in [15, 16, 17, 31, 33] {
+        let result = Aes256GcmSiv::new_from_slice(&[0u8; 33][..len]).map_err(GcmSivError::from);
+        assert_eq!(result.err(), Some(GcmSivError::InvalidKeyLength));
+    }
+
+    assert!(Aes128GcmSiv::new_from_slice(&[0u8; 16]).is_ok());
+    assert!(Aes256GcmSiv::new_from_slice(&[0u8; 32]).is_ok());
+}
@@ -0,0 +1,164 @@
+//! Tests for `EncryptOptions` and the `encrypt_with`/`decrypt_with`
+//! methods which consume them, along with `AuthFailurePolicy`.
+//!
+//! Results are compared against [`Aead::encrypt`], which the `tests!`
+//! macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{
+    Aes256GcmSiv, AuthFailurePolicy, EncryptOptions, GcmSivError, Nonce, TagPosition,
+};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+/// Plaintexts covering the empty, partial-block and multi-block cases.
+const PLAINTEXTS: &[&[u8]] = &[b"", b"x", b"exactly sixteen!", &[0x42; 33]];
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+fn payload(msg: &[u8]) -> Payload<'_, 'static> {
+    Payload { msg, aad: AAD }
+}
+
+#[test]
+fn encrypt_with_options() {
+    let (cipher, nonce) = setup();
+
+    for plaintext in PLAINTEXTS {
+        let expected = cipher.encrypt(nonce, payload(plaintext)).unwrap();
+        let (ciphertext, tag) = expected.split_at(plaintext.len());
+
+        // Default options match `Aead::encrypt`
+        let options = EncryptOptions::new();
+        let mut out = vec![0xff; 3];
+        cipher
+            .encrypt_with(nonce, payload(plaintext), &mut out, &options)
+            .unwrap();
+        assert_eq!(expected, out);
+
+        let mut decrypted = Vec::new();
+        cipher
+            .decrypt_with(nonce, payload(&out), None, &mut decrypted, &options)
+            .unwrap();
+        assert_eq!(*plaintext, decrypted);
+
+        // Tag prefix
+        let options = EncryptOptions::new().tag_position(TagPosition::Prefix);
+        cipher
+            .encrypt_with(nonce, payload(plaintext), &mut out, &options)
+            .unwrap();
+        assert_eq!(tag, &out[..16]);
+        assert_eq!(ciphertext, &out[16..]);
+
+        cipher
+            .decrypt_with(nonce, payload(&out), None, &mut decrypted, &options)
+            .unwrap();
+        assert_eq!(*plaintext, decrypted);
+
+        // Detached tag, appended to existing output
+        let options = EncryptOptions::new().detached_tag(true).append(true);
+        let mut out = b"header".to_vec();
+        let detached = cipher
+            .encrypt_with(nonce, payload(plaintext), &mut out, &options)
+            .unwrap();
+        assert_eq!(tag, detached.as_slice());
+        assert_eq!(b"header", &out[..6]);
+        assert_eq!(ciphertext, &out[6..]);
+
+        let mut decrypted = b"header".to_vec();
+        cipher
+            .decrypt_with(
+                nonce,
+                payload(ciphertext),
+                Some(&detached),
+                &mut decrypted,
+                &options,
+            )
+            .unwrap();
+        assert_eq!(b"header", &decrypted[..6]);
+        assert_eq!(*plaintext, &decrypted[6..]);
+
+        // Tag must be supplied exactly when detached
+        assert_eq!(
+            cipher.decrypt_with(nonce, payload(ciphertext), None, &mut decrypted, &options),
+            Err(GcmSivError::TagPlacement)
+        );
+    }
+}
+
+#[test]
+fn decrypt_with_auth_failure() {
+    let (cipher, nonce) = setup();
+
+    let mut ciphertext = cipher.encrypt(nonce, payload(PLAINTEXTS[3])).unwrap();
+    *ciphertext.last_mut().unwrap() ^= 1;
+    let ciphertext_len = ciphertext.len() - 16;
+
+    let (ciphertext, tag) = ciphertext.split_at(ciphertext_len);
+    let tag = GenericArray::from_slice(tag);
+
+    for (policy, expected) in [
+        (AuthFailurePolicy::Reencrypt, ciphertext),
+        (AuthFailurePolicy::Zero, &[0u8; 64][..ciphertext_len]),
+    ] {
+        let mut buffer = ciphertext.to_vec();
+        assert_eq!(
+            cipher.decrypt_in_place_detached_with(nonce, AAD, &mut buffer, tag, policy),
+            Err(GcmSivError::AuthFailed)
+        );
+        assert_eq!(expected, buffer);
+    }
+
+    // Skipping re-encryption leaves unauthenticated plaintext behind
+    let mut buffer = ciphertext.to_vec();
+    assert_eq!(
+        cipher.decrypt_in_place_detached_with(
+            nonce,
+            AAD,
+            &mut buffer,
+            tag,
+            AuthFailurePolicy::Skip
+        ),
+        Err(GcmSivError::AuthFailed)
+    );
+    assert_ne!(ciphertext, buffer);
+
+    // `decrypt_with` truncates the output back to its original length
+    // whatever the policy
+    for policy in [
+        AuthFailurePolicy::Reencrypt,
+        AuthFailurePolicy::Zero,
+        AuthFailurePolicy::Skip,
+    ] {
+        let options = EncryptOptions::new()
+            .detached_tag(true)
+            .append(true)
+            .auth_failure(policy);
+        let mut out = b"header".to_vec();
+        assert_eq!(
+            cipher.decrypt_with(nonce, payload(ciphertext), Some(tag), &mut out, &options),
+            Err(GcmSivError::AuthFailed)
+        );
+        assert_eq!(out, b"header");
+    }
+
+    let options = EncryptOptions::new().tag_position(TagPosition::Prefix);
+    assert_eq!(
+        cipher.decrypt_with(
+            nonce,
+            payload(&ciphertext[..15]),
+            None,
+            &mut Vec::new(),
+            &options
+        ),
+        Err(GcmSivError::CiphertextTooShort)
+    );
+}
@@ -0,0 +1,84 @@
+//! Per-nonce subkey reuse tests
+
+#![cfg(feature = "aes")]
+
+use aes_gcm_siv::aead::{AeadInPlace, KeyInit};
+use aes_gcm_siv::{Aes128GcmSiv, Aes256GcmSiv, GcmSivError, Nonce};
+
+const AAD: &[u8] = b"associated data";
+const PLAINTEXT: &[u8] = b"a message spanning more than one POLYVAL block";
+
+#[test]
+fn matches_cipher() {
+    let cipher = Aes256GcmSiv::new(&[0x42; 32].into());
+    let nonce = Nonce::from_slice(b"unique nonce");
+    let keys = cipher.per_nonce_keys(nonce);
+
+    let mut expected = PLAINTEXT.to_vec();
+    let expected_tag = cipher
+        .encrypt_in_place_detached(nonce, AAD, &mut expected)
+        .unwrap();
+
+    // Repeated operations all agree with a freshly derived cipher
+    for _ in 0..3 {
+        let mut buffer = PLAINTEXT.to_vec();
+        let tag = keys.encrypt_in_place_detached(AAD, &mut buffer).unwrap();
+        assert_eq!(tag, expected_tag);
+        assert_eq!(buffer, expected);
+
+        assert_eq!(keys.compute_tag(AAD, PLAINTEXT).unwrap(), expected_tag);
+        keys.verify_detached(AAD, &buffer, &tag).unwrap();
+
+        keys.decrypt_in_place_detached(AAD, &mut buffer, &tag)
+            .unwrap();
+        assert_eq!(buffer, PLAINTEXT);
+    }
+}
+
+#[test]
+fn state_not_carried_over() {
+    let cipher = Aes128GcmSiv::new(&Default::default());
+    let keys = cipher.per_nonce_keys(&Default::default());
+
+    let tag = keys.compute_tag(AAD, PLAINTEXT).unwrap();
+
+    let mut buffer = PLAINTEXT.to_vec();
+    buffer[0] ^= 1;
+    let bad_tag = keys.encrypt_in_place_detached(AAD, &mut buffer).unwrap();
+    assert_ne!(bad_tag, tag);
+
+    // A failed decryption leaves the ciphertext intact and doesn't affect
+    // the next operation
+    let mut buffer = PLAINTEXT.to_vec();
+    keys.encrypt_in_place_detached(AAD, &mut buffer).unwrap();
+    let ciphertext = buffer.clone();
+    assert_eq!(
+        keys.decrypt_in_place_detached(AAD, &mut buffer, &bad_tag),
+        Err(GcmSivError::AuthFailed)
+    );
+    assert_eq!(buffer, ciphertext);
+    assert_eq!(
+        keys.verify_detached(AAD, &buffer, &bad_tag),
+        Err(GcmSivError::AuthFailed)
+    );
+
+    keys.decrypt_in_place_detached(AAD, &mut buffer, &tag)
+        .unwrap();
+    assert_eq!(buffer, PLAINTEXT);
+}
+
+#[test]
+fn nonce_bound() {
+    let cipher = Aes256GcmSiv::new(&Default::default());
+    let keys = cipher.per_nonce_keys(Nonce::from_slice(b"first nonce!"));
+
+    let mut buffer = PLAINTEXT.to_vec();
+    let tag = cipher
+        .encrypt_in_place_detached(Nonce::from_slice(b"other nonce!"), AAD, &mut buffer)
+        .unwrap();
+
+    assert_eq!(
+        keys.decrypt_in_place_detached(AAD, &mut buffer, &tag),
+        Err(GcmSivError::AuthFailed)
+    );
+}
@@ -0,0 +1,51 @@
+//! Tests for the tag-first ciphertext layout.
+//!
+//! Results are compared against [`Aead::encrypt`], which the `tests!`
+//! macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, GcmSivError, Nonce};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+/// Plaintexts covering the empty, partial-block and multi-block cases.
+const PLAINTEXTS: &[&[u8]] = &[b"", b"x", b"exactly sixteen!", &[0x42; 33]];
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+fn payload(msg: &[u8]) -> Payload<'_, 'static> {
+    Payload { msg, aad: AAD }
+}
+
+#[test]
+fn prefixed() {
+    let (cipher, nonce) = setup();
+
+    for plaintext in PLAINTEXTS {
+        let expected = cipher.encrypt(nonce, payload(plaintext)).unwrap();
+        let (ciphertext, tag) = expected.split_at(plaintext.len());
+
+        // Same tag and ciphertext bytes as the suffixed layout
+        let prefixed = cipher.encrypt_prefixed(nonce, payload(plaintext)).unwrap();
+        assert_eq!(tag, &prefixed[..16]);
+        assert_eq!(ciphertext, &prefixed[16..]);
+
+        let decrypted = cipher.decrypt_prefixed(nonce, payload(&prefixed)).unwrap();
+        assert_eq!(*plaintext, decrypted.as_slice());
+    }
+
+    for len in 0..16 {
+        assert_eq!(
+            cipher.decrypt_prefixed(nonce, &[0u8; 16][..len]),
+            Err(GcmSivError::CiphertextTooShort)
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! Check that in-place encryption of a `Vec` with spare capacity doesn't
+//! allocate.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, AeadInPlace, KeyInit};
+use aes_gcm_siv::Aes256GcmSiv;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Allocator which counts allocations and reallocations.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn vec_with_spare_capacity() {
+    let cipher = Aes256GcmSiv::new(GenericArray::from_slice(&[0x42; 32]));
+    let nonce = GenericArray::from_slice(b"unique nonce");
+    let plaintext = b"plaintext message";
+
+    let mut buffer = Vec::with_capacity(plaintext.len() + 16);
+    buffer.extend_from_slice(plaintext);
+
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    cipher.encrypt_in_place(nonce, b"", &mut buffer).unwrap();
+    cipher.decrypt_in_place(nonce, b"", &mut buffer).unwrap();
+    assert_eq!(ALLOCATIONS.load(Ordering::SeqCst), before);
+
+    assert_eq!(&buffer, plaintext);
+}
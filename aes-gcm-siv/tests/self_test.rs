@@ -0,0 +1,8 @@
+//! Known-answer self-test
+
+#![cfg(feature = "self-test")]
+
+#[test]
+fn run_rfc8452_vectors() {
+    aes_gcm_siv::run_rfc8452_vectors().unwrap();
+}
@@ -0,0 +1,72 @@
+//! Tests for in-place encryption of a message split across two buffers.
+//!
+//! Results are compared against [`Aead::encrypt`], which the `tests!`
+//! macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, Nonce};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+/// Plaintexts covering the empty, partial-block and multi-block cases.
+const PLAINTEXTS: &[&[u8]] = &[b"", b"x", b"exactly sixteen!", &[0x42; 33]];
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+fn payload(msg: &[u8]) -> Payload<'_, 'static> {
+    Payload { msg, aad: AAD }
+}
+
+#[test]
+fn split_in_place_detached() {
+    let (cipher, nonce) = setup();
+
+    for plaintext in PLAINTEXTS {
+        let expected = cipher.encrypt(nonce, payload(plaintext)).unwrap();
+        let (ciphertext, tag) = expected.split_at(plaintext.len());
+
+        for split in 0..=plaintext.len() {
+            let mut buffer = plaintext.to_vec();
+            let (head, tail) = buffer.split_at_mut(split);
+            let actual_tag = cipher
+                .encrypt_in_place_split_detached(nonce, AAD, head, tail)
+                .unwrap();
+            assert_eq!(tag, &actual_tag[..]);
+            assert_eq!(ciphertext, buffer.as_slice());
+
+            let (head, tail) = buffer.split_at_mut(split);
+            cipher
+                .decrypt_in_place_split_detached(nonce, AAD, head, tail, &actual_tag)
+                .unwrap();
+            assert_eq!(*plaintext, buffer.as_slice());
+        }
+    }
+}
+
+#[test]
+fn split_in_place_modified() {
+    let (cipher, nonce) = setup();
+
+    let mut ciphertext = cipher.encrypt(nonce, payload(PLAINTEXTS[3])).unwrap();
+    let tag = GenericArray::clone_from_slice(&ciphertext[PLAINTEXTS[3].len()..]);
+    ciphertext.truncate(PLAINTEXTS[3].len());
+
+    // Tweak the first byte
+    ciphertext[0] ^= 0xaa;
+
+    // A failed decryption leaves both halves of the ciphertext unmodified
+    let mut buffer = ciphertext.clone();
+    let (head, tail) = buffer.split_at_mut(ciphertext.len() / 2);
+    assert!(cipher
+        .decrypt_in_place_split_detached(nonce, AAD, head, tail, &tag)
+        .is_err());
+    assert_eq!(ciphertext, buffer);
+}
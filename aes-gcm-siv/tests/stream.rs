@@ -0,0 +1,141 @@
+//! STREAM construction tests
+
+#![cfg(all(feature = "aes", feature = "alloc", feature = "stream"))]
+
+use aes_gcm_siv::aead::{
+    generic_array::GenericArray,
+    stream::{DecryptorBE32, EncryptorBE32},
+};
+use aes_gcm_siv::Aes256GcmSiv;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+const KEY: &[u8; 32] = &[0x42; 32];
+const NONCE: &[u8; 7] = b"stream!";
+
+/// Encrypt a 1 MiB message as a sequence of 64 KiB STREAM segments.
+fn encrypt_chunks(plaintext: &[u8]) -> Vec<Vec<u8>> {
+    let mut chunks = plaintext.chunks(CHUNK_SIZE);
+    let last = chunks.next_back().unwrap();
+    let mut encryptor = EncryptorBE32::<Aes256GcmSiv>::new(
+        GenericArray::from_slice(KEY),
+        GenericArray::from_slice(NONCE),
+    );
+
+    let mut ciphertext: Vec<_> = chunks
+        .map(|chunk| encryptor.encrypt_next(chunk).unwrap())
+        .collect();
+    ciphertext.push(encryptor.encrypt_last(last).unwrap());
+    ciphertext
+}
+
+fn decryptor() -> DecryptorBE32<Aes256GcmSiv> {
+    DecryptorBE32::new(
+        GenericArray::from_slice(KEY),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+fn plaintext() -> Vec<u8> {
+    (0..1024 * 1024).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn round_trip() {
+    let plaintext = plaintext();
+    let ciphertext = encrypt_chunks(&plaintext);
+    assert_eq!(ciphertext.len(), 16);
+
+    let (last, chunks) = ciphertext.split_last().unwrap();
+    let mut decryptor = decryptor();
+    let mut decrypted = Vec::new();
+
+    for chunk in chunks {
+        decrypted.extend(decryptor.decrypt_next(chunk.as_slice()).unwrap());
+    }
+
+    decrypted.extend(decryptor.decrypt_last(last.as_slice()).unwrap());
+    assert_eq!(plaintext, decrypted);
+}
+
+#[test]
+fn truncated_stream_rejected() {
+    let ciphertext = encrypt_chunks(&plaintext());
+
+    // Drop the final segment: the new last segment wasn't encrypted with the
+    // last-block flag set, so it must fail to decrypt as the last segment
+    let (last, chunks) = ciphertext[..ciphertext.len() - 1].split_last().unwrap();
+    let mut decryptor = decryptor();
+
+    for chunk in chunks {
+        decryptor.decrypt_next(chunk.as_slice()).unwrap();
+    }
+
+    assert!(decryptor.decrypt_last(last.as_slice()).is_err());
+}
+
+#[test]
+fn empty_stream() {
+    let encryptor = EncryptorBE32::<Aes256GcmSiv>::new(
+        GenericArray::from_slice(KEY),
+        GenericArray::from_slice(NONCE),
+    );
+    let ciphertext = encryptor.encrypt_last(&b""[..]).unwrap();
+    assert_eq!(ciphertext.len(), 16);
+
+    assert!(decryptor()
+        .decrypt_last(ciphertext.as_slice())
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn single_segment() {
+    let plaintext = &plaintext()[..1000];
+    let ciphertext = encrypt_chunks(plaintext);
+    assert_eq!(ciphertext.len(), 1);
+
+    let decrypted = decryptor().decrypt_last(ciphertext[0].as_slice()).unwrap();
+    assert_eq!(plaintext, decrypted.as_slice());
+
+    // A single segment isn't a valid non-final segment
+    assert!(decryptor().decrypt_next(ciphertext[0].as_slice()).is_err());
+}
+
+#[test]
+fn tampered_segment_rejected() {
+    let mut ciphertext = encrypt_chunks(&plaintext());
+    ciphertext[3][0] ^= 0xaa;
+
+    let mut decryptor = decryptor();
+
+    for chunk in &ciphertext[..3] {
+        decryptor.decrypt_next(chunk.as_slice()).unwrap();
+    }
+
+    assert!(decryptor.decrypt_next(ciphertext[3].as_slice()).is_err());
+}
+
+#[test]
+fn reordered_segments_rejected() {
+    let mut ciphertext = encrypt_chunks(&plaintext());
+    ciphertext.swap(1, 2);
+
+    let mut decryptor = decryptor();
+    decryptor.decrypt_next(ciphertext[0].as_slice()).unwrap();
+    assert!(decryptor.decrypt_next(ciphertext[1].as_slice()).is_err());
+}
+
+#[test]
+fn spliced_segment_rejected() {
+    // Segment from another stream under the same key but a different nonce
+    let mut other = EncryptorBE32::<Aes256GcmSiv>::new(
+        GenericArray::from_slice(KEY),
+        GenericArray::from_slice(b"other!!"),
+    );
+    let spliced = other.encrypt_next(&plaintext()[..CHUNK_SIZE]).unwrap();
+
+    let ciphertext = encrypt_chunks(&plaintext());
+    let mut decryptor = decryptor();
+    decryptor.decrypt_next(ciphertext[0].as_slice()).unwrap();
+    assert!(decryptor.decrypt_next(spliced.as_slice()).is_err());
+}
@@ -0,0 +1,263 @@
+//! STREAM `std::io` adapter tests
+
+#![cfg(all(feature = "aes", feature = "std", feature = "stream"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, stream::DecryptorBE32, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, StreamDecryptReader, StreamEncryptWriter, StreamNonce};
+use std::io::{self, Read, Write};
+
+const KEY: &[u8; 32] = &[0x42; 32];
+const NONCE: &[u8; 7] = b"stream!";
+const AAD: &[u8] = b"associated data";
+
+fn cipher() -> Aes256GcmSiv {
+    Aes256GcmSiv::new(GenericArray::from_slice(KEY))
+}
+
+fn plaintext(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+/// Encrypt `plaintext` through the writer, in writes of `write_size` bytes.
+fn encrypt(plaintext: &[u8], segment_size: usize, write_size: usize) -> Vec<u8> {
+    let nonce = StreamNonce::from_slice(NONCE);
+    let mut writer = StreamEncryptWriter::new(cipher(), nonce, AAD, segment_size, Vec::new());
+
+    for chunk in plaintext.chunks(write_size) {
+        writer.write_all(chunk).unwrap();
+    }
+
+    writer.finish().unwrap()
+}
+
+/// Decrypt with the `aead` crate's STREAM decryptor.
+fn decrypt(ciphertext: &[u8], segment_size: usize) -> Result<Vec<u8>, aes_gcm_siv::Error> {
+    let mut decryptor = DecryptorBE32::from_aead(cipher(), GenericArray::from_slice(NONCE));
+    let mut segments = ciphertext.chunks(segment_size + 16).peekable();
+    let mut plaintext = Vec::new();
+
+    while let Some(segment) = segments.next() {
+        if segments.peek().is_some() {
+            plaintext.extend(decryptor.decrypt_next(Payload {
+                msg: segment,
+                aad: AAD,
+            })?);
+        } else {
+            plaintext.extend(decryptor.decrypt_last(Payload {
+                msg: segment,
+                aad: AAD,
+            })?);
+            return Ok(plaintext);
+        }
+    }
+
+    // An empty stream has no final segment
+    decryptor.decrypt_last(Payload { msg: &[], aad: AAD })
+}
+
+#[test]
+fn round_trip_odd_sizes() {
+    let plaintext = plaintext(3 * 1024 * 1024 + 7);
+
+    for (segment_size, write_size) in [(1000, 333), (4097, 65537), (65539, 1), (65536, 4096)] {
+        let ciphertext = encrypt(&plaintext, segment_size, write_size);
+        let segments = (plaintext.len() + segment_size - 1) / segment_size;
+        assert_eq!(ciphertext.len(), plaintext.len() + segments * 16);
+        assert_eq!(decrypt(&ciphertext, segment_size).unwrap(), plaintext);
+    }
+}
+
+#[test]
+fn exact_multiple_of_segment_size() {
+    let plaintext = plaintext(4 * 1000);
+    let ciphertext = encrypt(&plaintext, 1000, 1000);
+
+    // The final full segment carries the last-segment flag; no empty
+    // segment follows it
+    assert_eq!(ciphertext.len(), 4 * 1016);
+    assert_eq!(decrypt(&ciphertext, 1000).unwrap(), plaintext);
+}
+
+#[test]
+fn empty() {
+    let ciphertext = encrypt(b"", 1000, 1);
+    assert_eq!(ciphertext.len(), 16);
+    assert_eq!(decrypt(&ciphertext, 1000).unwrap(), b"");
+}
+
+#[test]
+fn unfinished_stream_rejected() {
+    let plaintext = plaintext(2500);
+    let nonce = StreamNonce::from_slice(NONCE);
+    let mut ciphertext = Vec::new();
+
+    {
+        let mut writer = StreamEncryptWriter::new(cipher(), nonce, AAD, 1000, &mut ciphertext);
+        writer.write_all(&plaintext).unwrap();
+    }
+
+    // Only the first two full segments were written, neither flagged last
+    assert_eq!(ciphertext.len(), 2 * 1016);
+    assert!(decrypt(&ciphertext, 1000).is_err());
+}
+
+/// Writer which accepts at most 10 bytes per write, and fails with
+/// [`io::ErrorKind::WouldBlock`] on the `fail_at`th call.
+struct Flaky {
+    data: Vec<u8>,
+    fail_at: usize,
+}
+
+impl Write for Flaky {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.fail_at = self.fail_at.wrapping_sub(1);
+        if self.fail_at == 0 {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        let n = buf.len().min(10);
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn writer_retry_after_error() {
+    let plaintext = plaintext(100);
+    let expected = encrypt(&plaintext, 16, 100);
+
+    for fail_at in 1..8 {
+        let nonce = StreamNonce::from_slice(NONCE);
+        let inner = Flaky {
+            data: Vec::new(),
+            fail_at,
+        };
+        let mut writer = StreamEncryptWriter::new(cipher(), nonce, AAD, 16, inner);
+
+        let mut remaining = &plaintext[..];
+        let mut failures = 0;
+
+        while !remaining.is_empty() {
+            match writer.write(remaining) {
+                Ok(n) => remaining = &remaining[n..],
+                Err(err) => {
+                    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+                    failures += 1;
+                }
+            }
+        }
+
+        assert_eq!(failures, 1);
+        assert_eq!(writer.finish().unwrap().data, expected);
+    }
+}
+
+/// Reader which returns at most `max` bytes per read.
+struct Trickle<'a> {
+    data: &'a [u8],
+    max: usize,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.max).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+fn reader(ciphertext: &[u8], segment_size: usize) -> StreamDecryptReader<aes::Aes256, &[u8]> {
+    let nonce = StreamNonce::from_slice(NONCE);
+    StreamDecryptReader::new(cipher(), nonce, AAD, segment_size, ciphertext)
+}
+
+#[test]
+fn reader_round_trip() {
+    let plaintext = plaintext(1024 * 1024 + 7);
+
+    for segment_size in [1000, 4096, 65539] {
+        let ciphertext = encrypt(&plaintext, segment_size, 4096);
+        let mut decrypted = Vec::new();
+        reader(&ciphertext, segment_size)
+            .read_to_end(&mut decrypted)
+            .unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    let mut decrypted = Vec::new();
+    reader(&encrypt(b"", 1000, 1), 1000)
+        .read_to_end(&mut decrypted)
+        .unwrap();
+    assert!(decrypted.is_empty());
+}
+
+#[test]
+fn reader_byte_at_a_time() {
+    let plaintext = plaintext(3000);
+    let ciphertext = encrypt(&plaintext, 1000, 1000);
+
+    // Short reads on both sides of the adapter
+    let nonce = StreamNonce::from_slice(NONCE);
+    let inner = Trickle {
+        data: &ciphertext,
+        max: 7,
+    };
+    let mut reader = StreamDecryptReader::new(cipher(), nonce, AAD, 1000, inner);
+
+    let mut decrypted = Vec::new();
+    let mut byte = [0u8; 1];
+
+    while reader.read(&mut byte).unwrap() == 1 {
+        decrypted.push(byte[0]);
+    }
+
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn reader_truncated_mid_segment() {
+    let plaintext = plaintext(3000);
+    let ciphertext = encrypt(&plaintext, 1000, 1000);
+    let mut reader = reader(&ciphertext[..1500], 1000);
+
+    // The first segment is intact and returned before the failure
+    let mut decrypted = vec![0u8; 1000];
+    reader.read_exact(&mut decrypted).unwrap();
+    assert_eq!(decrypted, plaintext[..1000]);
+
+    let err = reader.read(&mut [0u8; 1]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn reader_missing_last_segment() {
+    let ciphertext = encrypt(&plaintext(3000), 1000, 1000);
+    let mut decrypted = Vec::new();
+    let err = reader(&ciphertext[..2 * 1016], 1000)
+        .read_to_end(&mut decrypted)
+        .unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(decrypted.len(), 1000);
+}
+
+#[test]
+fn reader_poisoned_after_failure() {
+    let mut ciphertext = encrypt(&plaintext(3000), 1000, 1000);
+    ciphertext[1016] ^= 0xaa;
+    let mut reader = reader(&ciphertext, 1000);
+
+    let mut decrypted = Vec::new();
+    assert!(reader.read_to_end(&mut decrypted).is_err());
+    assert_eq!(decrypted.len(), 1000);
+
+    for _ in 0..2 {
+        let err = reader.read(&mut [0u8; 16]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,90 @@
+//! Tests for the two-pass `StreamingEncryptor`.
+//!
+//! Results are compared against [`Aead::encrypt`], which the `tests!`
+//! macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, GcmSivError, Nonce, StreamingEncryptor};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+/// Plaintexts covering the empty, partial-block and multi-block cases.
+const PLAINTEXTS: &[&[u8]] = &[b"", b"x", b"exactly sixteen!", &[0x42; 33]];
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+#[test]
+fn streaming_encryptor() {
+    let (cipher, nonce) = setup();
+
+    for plaintext in PLAINTEXTS {
+        let payload = Payload {
+            msg: plaintext,
+            aad: AAD,
+        };
+        let expected = cipher.encrypt(nonce, payload).unwrap();
+        let (ciphertext, tag) = expected.split_at(plaintext.len());
+
+        for chunk_size in &[1, 15, 16, 17] {
+            let mut encryptor = StreamingEncryptor::new(&cipher, nonce, AAD).unwrap();
+
+            for chunk in plaintext.chunks(*chunk_size) {
+                encryptor.update(chunk).unwrap();
+            }
+
+            assert_eq!(tag, &encryptor.finish().unwrap()[..]);
+
+            // Chunk boundaries may differ between the two passes
+            let mut buffer = plaintext.to_vec();
+            for chunk in buffer.chunks_mut(chunk_size + 1) {
+                encryptor.encrypt_chunk(chunk).unwrap();
+            }
+
+            encryptor.finalize().unwrap();
+            assert_eq!(ciphertext, buffer.as_slice());
+        }
+    }
+}
+
+#[test]
+fn streaming_encryptor_mismatch() {
+    let (cipher, nonce) = setup();
+    let plaintext = PLAINTEXTS[3];
+    let mut buffer = plaintext.to_vec();
+
+    let mut encryptor = StreamingEncryptor::new(&cipher, nonce, AAD).unwrap();
+    encryptor.update(plaintext).unwrap();
+    assert_eq!(
+        encryptor.encrypt_chunk(&mut buffer),
+        Err(GcmSivError::StreamMismatch)
+    );
+
+    encryptor.finish().unwrap();
+    assert_eq!(
+        encryptor.update(plaintext),
+        Err(GcmSivError::StreamMismatch)
+    );
+
+    // Second pass shorter than the first
+    let (head, tail) = buffer.split_at_mut(1);
+    encryptor.encrypt_chunk(head).unwrap();
+    assert_eq!(encryptor.finalize(), Err(GcmSivError::StreamMismatch));
+
+    // Second pass longer than the first
+    let mut encryptor = StreamingEncryptor::new(&cipher, nonce, AAD).unwrap();
+    encryptor.update(&plaintext[1..]).unwrap();
+    encryptor.finish().unwrap();
+    encryptor.encrypt_chunk(tail).unwrap();
+    assert_eq!(
+        encryptor.encrypt_chunk(&mut [0]),
+        Err(GcmSivError::StreamMismatch)
+    );
+}
@@ -0,0 +1,84 @@
+//! Tests for encrypting and decrypting into caller-provided output slices.
+//!
+//! Results are compared against the [`Aead`] methods, which the `tests!`
+//! macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, GcmSivError, Nonce};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+/// Plaintexts covering the empty, partial-block and multi-block cases.
+const PLAINTEXTS: &[&[u8]] = &[b"", b"x", b"exactly sixteen!", &[0x42; 33]];
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+fn payload(msg: &[u8]) -> Payload<'_, 'static> {
+    Payload { msg, aad: AAD }
+}
+
+#[test]
+fn encrypt_to_slice() {
+    let (cipher, nonce) = setup();
+
+    for plaintext in PLAINTEXTS {
+        let expected = cipher.encrypt(nonce, payload(plaintext)).unwrap();
+
+        // Oversized output buffers are allowed
+        let mut out = vec![0u8; expected.len() + 1];
+        let len = cipher
+            .encrypt_to_slice(nonce, AAD, plaintext, &mut out)
+            .unwrap();
+        assert_eq!(expected, &out[..len]);
+
+        let short = &mut out[..expected.len() - 1];
+        assert_eq!(
+            cipher.encrypt_to_slice(nonce, AAD, plaintext, short),
+            Err(GcmSivError::BufferTooSmall)
+        );
+    }
+}
+
+#[test]
+fn decrypt_to_slice() {
+    let (cipher, nonce) = setup();
+
+    for plaintext in PLAINTEXTS {
+        let mut ciphertext = cipher.encrypt(nonce, payload(plaintext)).unwrap();
+
+        let mut out = vec![0u8; plaintext.len()];
+        let len = cipher
+            .decrypt_to_slice(nonce, AAD, &ciphertext, &mut out)
+            .unwrap();
+        assert_eq!(*plaintext, &out[..len]);
+
+        if !plaintext.is_empty() {
+            let short = &mut out[..plaintext.len() - 1];
+            assert_eq!(
+                cipher.decrypt_to_slice(nonce, AAD, &ciphertext, short),
+                Err(GcmSivError::BufferTooSmall)
+            );
+        }
+
+        assert_eq!(
+            cipher.decrypt_to_slice(nonce, AAD, &ciphertext[..15], &mut out),
+            Err(GcmSivError::CiphertextTooShort)
+        );
+
+        // Tamper with the tag: no plaintext may be left in `out`
+        *ciphertext.last_mut().unwrap() ^= 0x01;
+        assert_eq!(
+            cipher.decrypt_to_slice(nonce, AAD, &ciphertext, &mut out),
+            Err(GcmSivError::AuthFailed)
+        );
+        assert!(out.iter().all(|&b| b == 0));
+    }
+}
@@ -0,0 +1,62 @@
+//! Nonce reuse tracking tests
+
+#![cfg(all(feature = "aes", feature = "std"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit};
+use aes_gcm_siv::{Aes256GcmSiv, GcmSivError, NonceReusePolicy, TrackedCipher};
+
+fn tracked(policy: NonceReusePolicy) -> TrackedCipher<aes::Aes256> {
+    TrackedCipher::new(Aes256GcmSiv::new(&Default::default()), policy)
+}
+
+#[test]
+fn matches_untracked() {
+    let cipher = tracked(NonceReusePolicy::Error);
+    let nonce = GenericArray::from_slice(b"unique nonce");
+
+    let ciphertext = cipher.encrypt(nonce, &b"plaintext"[..]).unwrap();
+    let expected = Aes256GcmSiv::new(&Default::default())
+        .encrypt(nonce, &b"plaintext"[..])
+        .unwrap();
+    assert_eq!(ciphertext, expected);
+
+    // Decryption doesn't count as reuse
+    assert_eq!(
+        cipher.decrypt(nonce, ciphertext.as_slice()).unwrap(),
+        b"plaintext"
+    );
+    assert_eq!(
+        cipher.decrypt(nonce, ciphertext.as_slice()).unwrap(),
+        b"plaintext"
+    );
+}
+
+#[test]
+fn reuse_error() {
+    let cipher = tracked(NonceReusePolicy::Error);
+    let nonce = GenericArray::from_slice(b"unique nonce");
+
+    cipher.encrypt(nonce, &b"first"[..]).unwrap();
+    assert!(cipher.encrypt(nonce, &b"second"[..]).is_err());
+    assert_eq!(cipher.check_nonce(nonce), Err(GcmSivError::NonceReused));
+
+    cipher
+        .encrypt(GenericArray::from_slice(b"other nonce!"), &b"third"[..])
+        .unwrap();
+    assert_eq!(cipher.reuse_count(), 0);
+}
+
+#[test]
+fn reuse_count() {
+    let cipher = tracked(NonceReusePolicy::Count);
+    let nonce = GenericArray::from_slice(b"unique nonce");
+
+    for _ in 0..3 {
+        cipher.encrypt(nonce, &b"message"[..]).unwrap();
+    }
+
+    cipher
+        .encrypt(GenericArray::from_slice(b"other nonce!"), &b"message"[..])
+        .unwrap();
+    assert_eq!(cipher.reuse_count(), 2);
+}
@@ -0,0 +1,65 @@
+//! Fallible allocation tests
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit};
+use aes_gcm_siv::{Aes256GcmSiv, GcmSivError};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Allocator which fails any allocation larger than [`LIMIT`].
+struct LimitedAllocator;
+
+static LIMIT: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+unsafe impl GlobalAlloc for LimitedAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.size() > LIMIT.load(Ordering::SeqCst) {
+            return ptr::null_mut();
+        }
+
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: LimitedAllocator = LimitedAllocator;
+
+const MSG_LEN: usize = 1024 * 1024;
+
+#[test]
+fn try_encrypt_decrypt() {
+    let cipher = Aes256GcmSiv::new(GenericArray::from_slice(&[0x42; 32]));
+    let nonce = GenericArray::from_slice(b"unique nonce");
+    let plaintext = vec![0x55; MSG_LEN];
+
+    let ciphertext = cipher.try_encrypt(nonce, plaintext.as_slice()).unwrap();
+    assert_eq!(
+        ciphertext,
+        cipher.encrypt(nonce, plaintext.as_slice()).unwrap()
+    );
+    assert_eq!(
+        plaintext,
+        cipher.try_decrypt(nonce, ciphertext.as_slice()).unwrap()
+    );
+
+    // Allocations of the message size now fail
+    LIMIT.store(MSG_LEN / 2, Ordering::SeqCst);
+    let encrypt_result = cipher.try_encrypt(nonce, plaintext.as_slice());
+    let decrypt_result = cipher.try_decrypt(nonce, ciphertext.as_slice());
+    LIMIT.store(usize::MAX, Ordering::SeqCst);
+
+    assert_eq!(encrypt_result, Err(GcmSivError::AllocationFailed));
+    assert_eq!(decrypt_result, Err(GcmSivError::AllocationFailed));
+
+    // Length errors are reported before attempting to allocate
+    assert_eq!(
+        cipher.try_decrypt(nonce, &ciphertext[..15]),
+        Err(GcmSivError::CiphertextTooShort)
+    );
+}
@@ -0,0 +1,54 @@
+//! Tests for encrypting and decrypting into uninitialized output buffers.
+//!
+//! Results are compared against [`Aead::encrypt`], which the `tests!`
+//! macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, GcmSivError, Nonce};
+use core::mem::MaybeUninit;
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+/// Plaintexts covering the empty, partial-block and multi-block cases.
+const PLAINTEXTS: &[&[u8]] = &[b"", b"x", b"exactly sixteen!", &[0x42; 33]];
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+#[test]
+fn encrypt_decrypt_uninit() {
+    let (cipher, nonce) = setup();
+
+    for plaintext in PLAINTEXTS {
+        let payload = Payload {
+            msg: plaintext,
+            aad: AAD,
+        };
+        let expected = cipher.encrypt(nonce, payload).unwrap();
+
+        let mut out = vec![MaybeUninit::uninit(); expected.len()];
+        let ciphertext = cipher
+            .encrypt_to_uninit(nonce, AAD, plaintext, &mut out)
+            .unwrap();
+        assert_eq!(expected, ciphertext);
+
+        let mut out = vec![MaybeUninit::uninit(); plaintext.len()];
+        let decrypted = cipher
+            .decrypt_to_uninit(nonce, AAD, &expected, &mut out)
+            .unwrap();
+        assert_eq!(*plaintext, decrypted);
+
+        let mut out = vec![MaybeUninit::uninit(); expected.len() - 1];
+        assert_eq!(
+            cipher.encrypt_to_uninit(nonce, AAD, plaintext, &mut out),
+            Err(GcmSivError::BufferTooSmall)
+        );
+    }
+}
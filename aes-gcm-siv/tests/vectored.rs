@@ -0,0 +1,112 @@
+//! Tests for associated data and plaintexts split across several slices.
+//!
+//! Results are compared against the contiguous [`Aead`] and [`AeadInPlace`]
+//! methods, which the `tests!` macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, AeadInPlace, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, Nonce};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const PLAINTEXT: &[u8] = b"plaintext message";
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+#[test]
+fn vectored_aad() {
+    let (cipher, nonce) = setup();
+
+    let aad: Vec<u8> = (0..40).collect();
+    let mut expected = PLAINTEXT.to_vec();
+    let expected_tag = cipher
+        .encrypt_in_place_detached(nonce, &aad, &mut expected)
+        .unwrap();
+
+    for i in 0..=aad.len() {
+        for j in i..=aad.len() {
+            let slices: &[&[u8]] = &[&aad[..i], &[], &aad[i..j], &aad[j..]];
+
+            let mut buffer = PLAINTEXT.to_vec();
+            let tag = cipher
+                .encrypt_in_place_detached_vectored(nonce, slices, &mut buffer)
+                .unwrap();
+            assert_eq!(expected_tag, tag);
+            assert_eq!(expected, buffer);
+
+            cipher
+                .decrypt_in_place_detached_vectored(nonce, slices, &mut buffer, &tag)
+                .unwrap();
+            assert_eq!(PLAINTEXT, buffer.as_slice());
+        }
+    }
+}
+
+#[test]
+fn encrypt_vectored_to_slice() {
+    let (cipher, nonce) = setup();
+    let aad = b"associated data";
+
+    for plaintext in &[&[][..], PLAINTEXT, &[0x42; 70]] {
+        let payload = Payload {
+            msg: plaintext,
+            aad,
+        };
+        let expected = cipher.encrypt(nonce, payload).unwrap();
+        let mut out = vec![0u8; expected.len()];
+
+        for segment_sizes in &[&[1][..], &[16], &[1, 15, 16, 17], &[7, 0, 32]] {
+            let mut segments = vec![];
+            let mut remaining = *plaintext;
+
+            for &size in segment_sizes.iter().cycle() {
+                if remaining.is_empty() {
+                    break;
+                }
+
+                let (segment, rest) = remaining.split_at(size.min(remaining.len()));
+                segments.push(segment);
+                remaining = rest;
+            }
+
+            let len = cipher
+                .encrypt_vectored_to_slice(nonce, aad, &segments, &mut out)
+                .unwrap();
+            assert_eq!(expected, &out[..len]);
+        }
+    }
+}
+
+#[test]
+fn vectored_aad_iter() {
+    let (cipher, nonce) = setup();
+
+    let aad: Vec<u8> = (0..40).collect();
+    let expected_tag = cipher
+        .encrypt_in_place_detached(nonce, &aad, &mut [])
+        .unwrap();
+
+    // `[a, b, c]` must authenticate the same as `a || b || c`
+    let pieces = [&aad[..3], &aad[3..20], &aad[20..]];
+    let tag = cipher
+        .encrypt_in_place_detached_vectored(nonce, pieces, &mut [])
+        .unwrap();
+    assert_eq!(expected_tag, tag);
+
+    for chunk_size in 1..=aad.len() {
+        let tag = cipher
+            .encrypt_in_place_detached_vectored(nonce, aad.chunks(chunk_size), &mut [])
+            .unwrap();
+        assert_eq!(expected_tag, tag);
+
+        let owned: Vec<Vec<u8>> = aad.chunks(chunk_size).map(<[u8]>::to_vec).collect();
+        cipher
+            .decrypt_in_place_detached_vectored(nonce, &owned, &mut [], &tag)
+            .unwrap();
+    }
+}
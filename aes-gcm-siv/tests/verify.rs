@@ -0,0 +1,57 @@
+//! Tests for authenticating ciphertexts and associated data without
+//! decrypting anything.
+//!
+//! Results are compared against the [`Aead`] methods, which the `tests!`
+//! macro checks against the RFC 8452 vectors.
+
+#![cfg(all(feature = "aes", feature = "alloc"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, Nonce};
+
+const NONCE: &[u8; 12] = b"unique nonce";
+const AAD: &[u8] = b"associated data";
+
+fn setup() -> (Aes256GcmSiv, &'static Nonce) {
+    (
+        Aes256GcmSiv::new(&[0x01; 32].into()),
+        GenericArray::from_slice(NONCE),
+    )
+}
+
+fn payload(msg: &[u8]) -> Payload<'_, 'static> {
+    Payload { msg, aad: AAD }
+}
+
+#[test]
+fn verify() {
+    let (cipher, nonce) = setup();
+    let mut ciphertext = cipher.encrypt(nonce, payload(&[0x42; 33])).unwrap();
+
+    assert!(cipher.verify(nonce, AAD, &ciphertext).is_ok());
+
+    // Every bit flip is rejected, just as it is by `decrypt`
+    for i in 0..ciphertext.len() {
+        ciphertext[i] ^= 0x01;
+        assert!(cipher.decrypt(nonce, payload(&ciphertext)).is_err());
+        assert!(cipher.verify(nonce, AAD, &ciphertext).is_err());
+        ciphertext[i] ^= 0x01;
+    }
+
+    // Inputs shorter than a tag are rejected by both
+    let truncated = &ciphertext[..15];
+    assert!(cipher.decrypt(nonce, truncated).is_err());
+    assert!(cipher.verify(nonce, b"", truncated).is_err());
+}
+
+#[test]
+fn authenticate() {
+    let (cipher, nonce) = setup();
+
+    // The tag is the entire ciphertext of an empty message
+    let tag = cipher.authenticate(nonce, AAD).unwrap();
+    assert_eq!(cipher.encrypt(nonce, payload(b"")).unwrap(), &tag[..]);
+
+    assert!(cipher.verify_tag(nonce, AAD, &tag).is_ok());
+    assert!(cipher.verify_tag(nonce, b"\x00", &tag).is_err());
+}
@@ -0,0 +1,124 @@
+//! Check that key material and plaintext returned by `decrypt_zeroizing`
+//! are wiped on drop.
+
+#![cfg(all(feature = "aes", feature = "alloc", feature = "zeroize"))]
+
+use aes_gcm_siv::aead::{generic_array::GenericArray, Aead, KeyInit};
+use aes_gcm_siv::{Aes128GcmSiv, Aes256GcmSiv};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Allocator which inspects allocations of size [`TRACKED_SIZE`] when they
+/// are freed, recording the largest number of non-zero bytes any of them
+/// still held.
+struct InspectingAllocator;
+
+static TRACKED_SIZE: AtomicUsize = AtomicUsize::new(0);
+static DIRTY: AtomicUsize = AtomicUsize::new(0);
+
+/// Serializes tests, since they share the tracking state above.
+static LOCKED: AtomicBool = AtomicBool::new(false);
+
+struct Lock;
+
+impl Lock {
+    fn acquire() -> Self {
+        while LOCKED
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            std::thread::yield_now();
+        }
+
+        Lock
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        LOCKED.store(false, Ordering::SeqCst);
+    }
+}
+
+unsafe impl GlobalAlloc for InspectingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if layout.size() == TRACKED_SIZE.load(Ordering::SeqCst) {
+            let contents = std::slice::from_raw_parts(ptr, layout.size());
+
+            let dirty = contents.iter().filter(|&&byte| byte != 0).count();
+            DIRTY.fetch_max(dirty, Ordering::SeqCst);
+        }
+
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: InspectingAllocator = InspectingAllocator;
+
+/// Unusual length so no unrelated allocation is inspected.
+const MSG_LEN: usize = 777;
+
+#[test]
+fn plaintext_zeroized_on_drop() {
+    let _lock = Lock::acquire();
+    let cipher = Aes256GcmSiv::new(GenericArray::from_slice(&[0x42; 32]));
+    let nonce = GenericArray::from_slice(b"unique nonce");
+    let ciphertext = cipher.encrypt(nonce, &[0x55; MSG_LEN][..]).unwrap();
+
+    TRACKED_SIZE.store(MSG_LEN, Ordering::SeqCst);
+
+    // Sanity check: an ordinary `Vec` is freed with its contents intact
+    drop(vec![0x55u8; MSG_LEN]);
+    assert_eq!(DIRTY.swap(0, Ordering::SeqCst), MSG_LEN);
+
+    let plaintext = cipher
+        .decrypt_zeroizing(nonce, ciphertext.as_slice())
+        .unwrap();
+    assert_eq!(plaintext.as_slice(), &[0x55; MSG_LEN][..]);
+    drop(plaintext);
+
+    TRACKED_SIZE.store(0, Ordering::SeqCst);
+    assert_eq!(DIRTY.load(Ordering::SeqCst), 0);
+}
+
+/// Drop a boxed value (and a clone of it), asserting both are wiped.
+///
+/// The autodetecting AES backends only wipe the key schedule in use. The
+/// AES-NI schedule fills about half of the union sized for the software
+/// one, and the rest may hold uninitialized residue, whereas an unwiped key
+/// schedule is almost entirely non-zero.
+fn assert_wiped_on_drop<T: Clone>(value: Box<T>) {
+    let size = std::mem::size_of::<T>();
+    let _lock = Lock::acquire();
+    TRACKED_SIZE.store(size, Ordering::SeqCst);
+
+    // Sanity check: an ordinary allocation of the same size is left dirty
+    drop(vec![0x55u8; size]);
+    assert_eq!(DIRTY.swap(0, Ordering::SeqCst), size);
+
+    let clone = value.clone();
+    drop(value);
+    drop(clone);
+
+    TRACKED_SIZE.store(0, Ordering::SeqCst);
+    assert!(DIRTY.load(Ordering::SeqCst) < size / 2);
+}
+
+#[test]
+fn aes128gcmsiv_key_zeroized_on_drop() {
+    assert_wiped_on_drop(Box::new(Aes128GcmSiv::new(GenericArray::from_slice(
+        &[0x42; 16],
+    ))));
+}
+
+#[test]
+fn aes256gcmsiv_key_zeroized_on_drop() {
+    assert_wiped_on_drop(Box::new(Aes256GcmSiv::new(GenericArray::from_slice(
+        &[0x42; 32],
+    ))));
+}
@@ -1,7 +1,7 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 
-use aes_gcm_siv::aead::{Aead, KeyInit};
-use aes_gcm_siv::{Aes128GcmSiv, Aes256GcmSiv};
+use aes_gcm_siv::aead::{Aead, AeadInPlace, KeyInit};
+use aes_gcm_siv::{Aes128GcmSiv, Aes256GcmSiv, EncryptContext};
 
 const KB: usize = 1024;
 
@@ -35,6 +35,92 @@ fn bench(c: &mut Benchmarker) {
             let cipher = Aes256GcmSiv::new(&Default::default());
             b.iter(|| cipher.decrypt(&Default::default(), &*buf))
         });
+
+        group.bench_function(BenchmarkId::new("encrypt-256-context", size), |b| {
+            let cipher = Aes256GcmSiv::new(&Default::default());
+            let mut context = EncryptContext::new();
+            b.iter(|| {
+                context
+                    .encrypt(&cipher, &Default::default(), &*buf)
+                    .map(|ct| ct.len())
+            })
+        });
+        group.bench_function(BenchmarkId::new("decrypt-256-context", size), |b| {
+            let cipher = Aes256GcmSiv::new(&Default::default());
+            let mut context = EncryptContext::new();
+            b.iter(|| {
+                context
+                    .decrypt(&cipher, &Default::default(), &*buf)
+                    .map(|pt| pt.len())
+            })
+        });
+    }
+
+    group.finish();
+
+    let mut group = c.benchmark_group("aes-gcm-siv-small");
+
+    for size in &[16, 64, 184] {
+        let buf = vec![0u8; *size];
+
+        group.throughput(Throughput::Bytes(*size as u64));
+
+        group.bench_function(BenchmarkId::new("encrypt-128-vec", size), |b| {
+            let cipher = Aes128GcmSiv::new(&Default::default());
+            b.iter(|| cipher.encrypt(&Default::default(), &*buf))
+        });
+        group.bench_function(BenchmarkId::new("encrypt-128-array", size), |b| {
+            let cipher = Aes128GcmSiv::new(&Default::default());
+            b.iter(|| cipher.encrypt_array::<200>(&Default::default(), b"", &buf))
+        });
+    }
+
+    group.finish();
+
+    let mut group = c.benchmark_group("aes-gcm-siv-per-nonce");
+
+    for size in &[16, 64, KB] {
+        let mut buf = vec![0u8; *size];
+
+        group.throughput(Throughput::Bytes(*size as u64));
+
+        group.bench_function(BenchmarkId::new("tag-then-encrypt-128", size), |b| {
+            let cipher = Aes128GcmSiv::new(&Default::default());
+            b.iter(|| {
+                let nonce = Default::default();
+                let tag = cipher.compute_tag(&nonce, b"", &buf).unwrap();
+                let ct_tag = cipher.encrypt_in_place_detached(&nonce, b"", &mut buf);
+                (tag, ct_tag)
+            })
+        });
+        group.bench_function(BenchmarkId::new("tag-then-encrypt-128-keys", size), |b| {
+            let cipher = Aes128GcmSiv::new(&Default::default());
+            let keys = cipher.per_nonce_keys(&Default::default());
+            b.iter(|| {
+                let tag = keys.compute_tag(b"", &buf).unwrap();
+                let ct_tag = keys.encrypt_in_place_detached(b"", &mut buf);
+                (tag, ct_tag)
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("tag-then-encrypt-256", size), |b| {
+            let cipher = Aes256GcmSiv::new(&Default::default());
+            b.iter(|| {
+                let nonce = Default::default();
+                let tag = cipher.compute_tag(&nonce, b"", &buf).unwrap();
+                let ct_tag = cipher.encrypt_in_place_detached(&nonce, b"", &mut buf);
+                (tag, ct_tag)
+            })
+        });
+        group.bench_function(BenchmarkId::new("tag-then-encrypt-256-keys", size), |b| {
+            let cipher = Aes256GcmSiv::new(&Default::default());
+            let keys = cipher.per_nonce_keys(&Default::default());
+            b.iter(|| {
+                let tag = keys.compute_tag(b"", &buf).unwrap();
+                let ct_tag = keys.encrypt_in_place_detached(b"", &mut buf);
+                (tag, ct_tag)
+            })
+        });
     }
 
     group.finish();